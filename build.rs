@@ -0,0 +1,45 @@
+use std::{env, process::Command, str};
+
+fn main() {
+  println!("cargo:rerun-if-changed=build.rs");
+  println!("cargo:rustc-check-cfg=cfg(cheap_clone_core_net)");
+  println!("cargo:rustc-check-cfg=cfg(cheap_clone_no_atomic_ptr)");
+  println!("cargo:rustc-check-cfg=cfg(cheap_clone_diagnostic_namespace)");
+
+  let minor = match rustc_minor_version() {
+    Some(minor) => minor,
+    None => return,
+  };
+
+  // `core::net` is stable since Rust 1.77.
+  if minor >= 77 {
+    println!("cargo:rustc-cfg=cheap_clone_core_net");
+  }
+
+  // `#[diagnostic::on_unimplemented]` is stable since Rust 1.78.
+  if minor >= 78 {
+    println!("cargo:rustc-cfg=cheap_clone_diagnostic_namespace");
+  }
+
+  // `cfg(target_has_atomic)` is stable since Rust 1.60, older compilers are assumed to
+  // target platforms with atomic pointers.
+  if minor >= 60 {
+    let has_atomic_ptr = env::var("CARGO_CFG_TARGET_HAS_ATOMIC")
+      .map(|widths| widths.split(',').any(|width| width == "ptr"))
+      .unwrap_or(false);
+    if !has_atomic_ptr {
+      println!("cargo:rustc-cfg=cheap_clone_no_atomic_ptr");
+    }
+  }
+}
+
+fn rustc_minor_version() -> Option<u32> {
+  let rustc = env::var_os("RUSTC")?;
+  let output = Command::new(rustc).arg("--version").output().ok()?;
+  let version = str::from_utf8(&output.stdout).ok()?;
+  let mut pieces = version.split('.');
+  if pieces.next() != Some("rustc 1") {
+    return None;
+  }
+  pieces.next()?.parse().ok()
+}
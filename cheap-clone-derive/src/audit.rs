@@ -0,0 +1,242 @@
+use proc_macro2::{Span, TokenStream};
+use quote::ToTokens;
+use syn::{
+  parse_quote,
+  spanned::Spanned,
+  visit_mut::{self, VisitMut},
+  Attribute, Block, Expr, ExprMethodCall, Ident, ImplItem, Item, Local, StmtMacro,
+};
+
+/// The attribute which opts the `.clone()` calls of an expression out of the audit.
+const ALLOW_EXPENSIVE: &str = "allow_expensive";
+
+pub(crate) fn expand(args: TokenStream, mut item: Item) -> syn::Result<TokenStream> {
+  if !args.is_empty() {
+    return Err(syn::Error::new(
+      args.span(),
+      "`#[audit]` does not take arguments",
+    ));
+  }
+
+  match &mut item {
+    Item::Fn(item) => audit_block(&mut item.block)?,
+    Item::Impl(item) => {
+      for item in &mut item.items {
+        if let ImplItem::Fn(item) = item {
+          audit_block(&mut item.block)?;
+        }
+      }
+    }
+    _ => {
+      return Err(syn::Error::new(
+        Span::call_site(),
+        "`#[audit]` only supports functions and impl blocks",
+      ))
+    }
+  }
+  Ok(item.into_token_stream())
+}
+
+/// Rewrites the `.clone()` calls of a function body, importing `CheapClone` if there are any.
+fn audit_block(block: &mut Block) -> syn::Result<()> {
+  let mut audit = Audit {
+    allowed: 0,
+    rewritten: false,
+    error: None,
+  };
+  audit.visit_block_mut(block);
+  if let Some(error) = audit.error {
+    return Err(error);
+  }
+  if audit.rewritten {
+    block.stmts.insert(
+      0,
+      parse_quote!(
+        #[allow(unused_imports)]
+        use ::cheap_clone::__private::AuditClone as _;
+      ),
+    );
+  }
+  Ok(())
+}
+
+struct Audit {
+  /// How many `#[allow_expensive]` expressions or statements enclose the current one.
+  allowed: usize,
+  rewritten: bool,
+  /// The `#[allow_expensive]` attributes in positions which are not supported.
+  error: Option<syn::Error>,
+}
+
+impl Audit {
+  /// Visits the children of an `#[allow_expensive]` expression or statement, which still
+  /// need their own `#[allow_expensive]` attributes removed.
+  fn allow(&mut self, visit: impl FnOnce(&mut Self)) {
+    self.allowed += 1;
+    visit(self);
+    self.allowed -= 1;
+  }
+}
+
+impl VisitMut for Audit {
+  fn visit_expr_mut(&mut self, expr: &mut Expr) {
+    if expr_attrs(expr).map_or(false, take_allow_expensive) {
+      return self.allow(|audit| visit_mut::visit_expr_mut(audit, expr));
+    }
+
+    visit_mut::visit_expr_mut(self, expr);
+    if self.allowed > 0 {
+      return;
+    }
+    if let Expr::MethodCall(call) = expr {
+      if is_clone_call(call) {
+        call.method = Ident::new("__audited_clone", call.method.span());
+        self.rewritten = true;
+      }
+    }
+  }
+
+  fn visit_local_mut(&mut self, local: &mut Local) {
+    if take_allow_expensive(&mut local.attrs) {
+      self.allow(|audit| visit_mut::visit_local_mut(audit, local));
+    } else {
+      visit_mut::visit_local_mut(self, local);
+    }
+  }
+
+  fn visit_stmt_macro_mut(&mut self, stmt: &mut StmtMacro) {
+    // Macro invocations are not audited, so there is nothing to allow.
+    take_allow_expensive(&mut stmt.attrs);
+    visit_mut::visit_stmt_macro_mut(self, stmt);
+  }
+
+  // Supported `#[allow_expensive]` attributes are removed before their children are visited,
+  // so the remaining ones are in other positions, e.g. on a match arm or a struct field.
+  fn visit_attribute_mut(&mut self, attr: &mut Attribute) {
+    if attr.path().is_ident(ALLOW_EXPENSIVE) {
+      let error = syn::Error::new(
+        attr.span(),
+        "`#[allow_expensive]` is only supported on expressions and `let` statements",
+      );
+      match &mut self.error {
+        Some(errors) => errors.combine(error),
+        None => self.error = Some(error),
+      }
+    }
+  }
+
+  // Nested items are not part of the audited function.
+  fn visit_item_mut(&mut self, _: &mut Item) {}
+}
+
+/// Removes `#[allow_expensive]` from `attrs`, returning whether it was present.
+fn take_allow_expensive(attrs: &mut Vec<Attribute>) -> bool {
+  let len = attrs.len();
+  attrs.retain(|attr| !attr.path().is_ident(ALLOW_EXPENSIVE));
+  attrs.len() != len
+}
+
+fn expr_attrs(expr: &mut Expr) -> Option<&mut Vec<Attribute>> {
+  Some(match expr {
+    Expr::Array(expr) => &mut expr.attrs,
+    Expr::Assign(expr) => &mut expr.attrs,
+    Expr::Async(expr) => &mut expr.attrs,
+    Expr::Await(expr) => &mut expr.attrs,
+    Expr::Binary(expr) => &mut expr.attrs,
+    Expr::Block(expr) => &mut expr.attrs,
+    Expr::Break(expr) => &mut expr.attrs,
+    Expr::Call(expr) => &mut expr.attrs,
+    Expr::Cast(expr) => &mut expr.attrs,
+    Expr::Closure(expr) => &mut expr.attrs,
+    Expr::Const(expr) => &mut expr.attrs,
+    Expr::Continue(expr) => &mut expr.attrs,
+    Expr::Field(expr) => &mut expr.attrs,
+    Expr::ForLoop(expr) => &mut expr.attrs,
+    Expr::Group(expr) => &mut expr.attrs,
+    Expr::If(expr) => &mut expr.attrs,
+    Expr::Index(expr) => &mut expr.attrs,
+    Expr::Infer(expr) => &mut expr.attrs,
+    Expr::Let(expr) => &mut expr.attrs,
+    Expr::Lit(expr) => &mut expr.attrs,
+    Expr::Loop(expr) => &mut expr.attrs,
+    Expr::Macro(expr) => &mut expr.attrs,
+    Expr::Match(expr) => &mut expr.attrs,
+    Expr::MethodCall(expr) => &mut expr.attrs,
+    Expr::Paren(expr) => &mut expr.attrs,
+    Expr::Path(expr) => &mut expr.attrs,
+    Expr::Range(expr) => &mut expr.attrs,
+    Expr::Reference(expr) => &mut expr.attrs,
+    Expr::Repeat(expr) => &mut expr.attrs,
+    Expr::Return(expr) => &mut expr.attrs,
+    Expr::Struct(expr) => &mut expr.attrs,
+    Expr::Try(expr) => &mut expr.attrs,
+    Expr::TryBlock(expr) => &mut expr.attrs,
+    Expr::Tuple(expr) => &mut expr.attrs,
+    Expr::Unary(expr) => &mut expr.attrs,
+    Expr::Unsafe(expr) => &mut expr.attrs,
+    Expr::While(expr) => &mut expr.attrs,
+    Expr::Yield(expr) => &mut expr.attrs,
+    // Newer expressions (e.g. `&raw const`) keep their attributes, which are then reported as
+    // unsupported.
+    _ => return None,
+  })
+}
+
+/// Matches `receiver.clone()`, but not e.g. `receiver.clone::<T>()` or `receiver.clone(arg)`.
+fn is_clone_call(call: &ExprMethodCall) -> bool {
+  call.method == "clone" && call.turbofish.is_none() && call.args.is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn audit(item: &str) -> syn::Result<String> {
+    expand(TokenStream::new(), syn::parse_str(item)?).map(|tokens| tokens.to_string())
+  }
+
+  #[test]
+  fn rewrites_clone_calls() {
+    let output = audit("fn f(a: &A) -> A { a.clone() }").unwrap();
+    assert!(output.contains("a . __audited_clone ()"), "{}", output);
+    assert!(output.contains("AuditClone"), "{}", output);
+  }
+
+  #[test]
+  fn allow_expensive_keeps_clone_calls() {
+    for body in [
+      "#[allow_expensive] (a.clone(), b.clone())",
+      "#[allow_expensive] [a.clone()]",
+      "#[allow_expensive] a.clone().len()",
+      "#[allow_expensive] { a.clone() }",
+      "{ #[allow_expensive] let c = a.clone(); c }",
+    ] {
+      let output = audit(&format!("fn f() {{ {} }}", body)).unwrap();
+      assert!(!output.contains("__audited_clone"), "{}", output);
+      assert!(!output.contains("allow_expensive"), "{}", output);
+    }
+  }
+
+  #[test]
+  fn allow_expensive_does_not_leak() {
+    let output = audit("fn f() { (#[allow_expensive] a.clone(), b.clone()) }").unwrap();
+    assert!(output.contains("a . clone ()"), "{}", output);
+    assert!(output.contains("b . __audited_clone ()"), "{}", output);
+  }
+
+  #[test]
+  fn rejects_allow_expensive_elsewhere() {
+    let err = audit(
+      "fn f() {
+        match a {
+          #[allow_expensive]
+          _ => a.clone(),
+        }
+      }",
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("only supported on"), "{}", err);
+    let start = err.span().start();
+    assert_eq!((start.line, start.column), (3, 10));
+  }
+}
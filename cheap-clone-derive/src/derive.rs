@@ -0,0 +1,134 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote, quote_spanned};
+use syn::{
+  spanned::Spanned, visit::Visit, Data, DeriveInput, Fields, Generics, Ident, Index, Type,
+};
+
+pub(crate) fn expand(input: DeriveInput) -> syn::Result<TokenStream> {
+  let name = &input.ident;
+
+  let (body, field_tys) = match &input.data {
+    Data::Struct(data) => {
+      let (pat, ctor, tys) = destructure(quote!(Self), &data.fields);
+      (quote!(match self { #pat => #ctor }), tys)
+    }
+    Data::Enum(data) => {
+      let mut tys = Vec::new();
+      let arms = data.variants.iter().map(|variant| {
+        let ident = &variant.ident;
+        let (pat, ctor, variant_tys) = destructure(quote!(Self::#ident), &variant.fields);
+        tys.extend(variant_tys);
+        quote!(#pat => #ctor,)
+      });
+      let arms = arms.collect::<Vec<_>>();
+      if arms.is_empty() {
+        (quote!(match *self {}), tys)
+      } else {
+        (quote!(match self { #(#arms)* }), tys)
+      }
+    }
+    Data::Union(data) => {
+      return Err(syn::Error::new(
+        data.union_token.span(),
+        "`CheapClone` cannot be derived for unions",
+      ))
+    }
+  };
+
+  // Only fields whose types mention a type parameter need a bound, the others are checked
+  // directly in the body with the span of the offending field. The `Clone` supertrait is
+  // repeated because `#[derive(Clone)]` adds its own bounds on the parameters.
+  let mut generics = input.generics.clone();
+  let mut bounds = field_tys
+    .iter()
+    .filter(|ty| mentions_type_param(ty, &input.generics))
+    .map(|ty| quote_spanned!(ty.span()=> #ty: ::cheap_clone::CheapClone))
+    .collect::<Vec<_>>();
+  if input.generics.type_params().next().is_some() {
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    bounds.push(quote!(#name #ty_generics: ::core::clone::Clone));
+  }
+  if !bounds.is_empty() {
+    let where_clause = generics.make_where_clause();
+    for bound in bounds {
+      where_clause.predicates.push(syn::parse2(bound)?);
+    }
+  }
+  let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+  let costs = field_tys
+    .iter()
+    .map(|ty| quote_spanned!(ty.span()=> .max(<#ty as ::cheap_clone::CheapClone>::COST)));
+
+  Ok(quote! {
+    impl #impl_generics ::cheap_clone::CheapClone for #name #ty_generics #where_clause {
+      const COST: ::cheap_clone::CloneCost = ::cheap_clone::CloneCost::Copy #(#costs)*;
+
+      #[inline]
+      fn cheap_clone(&self) -> Self {
+        #body
+      }
+    }
+  })
+}
+
+/// Returns a pattern binding every field by reference, the expression
+/// rebuilding the value from cheap clones of those bindings, and the field types.
+fn destructure(path: TokenStream, fields: &Fields) -> (TokenStream, TokenStream, Vec<&Type>) {
+  let bindings = fields
+    .iter()
+    .enumerate()
+    .map(|(idx, _)| format_ident!("__field{}", idx))
+    .collect::<Vec<Ident>>();
+  let clones = fields.iter().zip(&bindings).map(|(field, binding)| {
+    let ty = &field.ty;
+    quote_spanned!(ty.span()=> <#ty as ::cheap_clone::CheapClone>::cheap_clone(#binding))
+  });
+  let tys = fields.iter().map(|field| &field.ty).collect();
+
+  match fields {
+    Fields::Named(_) => {
+      let names = fields.iter().map(|field| &field.ident).collect::<Vec<_>>();
+      (
+        quote!(#path { #(#names: #bindings),* }),
+        quote!(#path { #(#names: #clones),* }),
+        tys,
+      )
+    }
+    Fields::Unnamed(_) => {
+      let indices = (0..bindings.len()).map(Index::from);
+      (
+        quote!(#path { #(#indices: #bindings),* }),
+        quote!(#path(#(#clones),*)),
+        tys,
+      )
+    }
+    Fields::Unit => (quote!(#path), quote!(#path), tys),
+  }
+}
+
+/// Whether `ty` refers to any of the type parameters declared in `generics`.
+fn mentions_type_param(ty: &Type, generics: &Generics) -> bool {
+  struct Finder<'a> {
+    params: Vec<&'a Ident>,
+    found: bool,
+  }
+
+  impl<'ast> Visit<'ast> for Finder<'_> {
+    fn visit_path(&mut self, path: &'ast syn::Path) {
+      if let Some(segment) = path.segments.first() {
+        if self.params.contains(&&segment.ident) {
+          self.found = true;
+        }
+      }
+      syn::visit::visit_path(self, path);
+    }
+  }
+
+  let mut finder = Finder {
+    params: generics.type_params().map(|param| &param.ident).collect(),
+    found: false,
+  };
+  finder.visit_type(ty);
+  finder.found
+}
@@ -0,0 +1,58 @@
+//! Derive macros for the [`cheap-clone`](https://docs.rs/cheap-clone) crate.
+#![deny(missing_docs)]
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput, Item, ItemStruct};
+
+mod audit;
+mod derive;
+mod shared;
+
+/// Derives `CheapClone` by calling `CheapClone::cheap_clone` on every field.
+///
+/// A field whose type does not implement `CheapClone` is a compile error pointing at that
+/// field. Bounds are only added for field types that mention a type parameter, and
+/// `CheapClone::COST` is the maximum of the costs of all fields.
+#[proc_macro_derive(CheapClone)]
+pub fn derive_cheap_clone(input: TokenStream) -> TokenStream {
+  let input = parse_macro_input!(input as DeriveInput);
+  derive::expand(input)
+    .unwrap_or_else(syn::Error::into_compile_error)
+    .into()
+}
+
+/// Turns a struct with named fields into a cheaply cloneable handle.
+///
+/// `#[shared] struct Foo { a: A, b: B }` moves the fields into a private `FooInner`
+/// and makes `Foo` a wrapper around `Arc<FooInner>`, implementing `Clone` and `CheapClone`
+/// for it together with a `Foo::new(a, b)` constructor and `a()`/`b()` accessors that keep
+/// the visibility of their fields. `#[shared(local)]` uses `Rc` instead of `Arc`.
+///
+/// Doc comments stay on `Foo`, `cfg` and `allow` attributes are applied to every generated item,
+/// and all other attributes (e.g. derives) are applied to `FooInner` only.
+#[proc_macro_attribute]
+pub fn shared(args: TokenStream, input: TokenStream) -> TokenStream {
+  let args = parse_macro_input!(args as shared::Args);
+  let input = parse_macro_input!(input as ItemStruct);
+  shared::expand(args, input)
+    .unwrap_or_else(syn::Error::into_compile_error)
+    .into()
+}
+
+/// Rejects expensive `.clone()` calls in a function or in the methods of an impl block.
+///
+/// Every `receiver.clone()` method call is rewritten to a call which requires `CheapClone`, so
+/// cloning a value which is not `CheapClone` is a compile error pointing at the call (it never
+/// falls back to copying a reference to the value). Annotate an expression or a `let`
+/// statement with `#[allow_expensive]` to keep its `.clone()` calls, the attribute is an error
+/// anywhere else.
+///
+/// Only method call syntax is audited: `Clone::clone(&x)`, calls inside macro invocations
+/// and nested items are left untouched.
+#[proc_macro_attribute]
+pub fn audit(args: TokenStream, input: TokenStream) -> TokenStream {
+  let input = parse_macro_input!(input as Item);
+  audit::expand(args.into(), input)
+    .unwrap_or_else(syn::Error::into_compile_error)
+    .into()
+}
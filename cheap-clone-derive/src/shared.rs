@@ -0,0 +1,139 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{
+  parse::{Parse, ParseStream},
+  spanned::Spanned,
+  Fields, Ident, ItemStruct,
+};
+
+/// Attributes of the struct which are applied to every generated item.
+const COMMON_ATTRS: &[&str] = &["cfg", "allow"];
+
+/// Arguments accepted by `#[shared(...)]`.
+pub(crate) struct Args {
+  local: bool,
+}
+
+impl Parse for Args {
+  fn parse(input: ParseStream) -> syn::Result<Self> {
+    if input.is_empty() {
+      return Ok(Self { local: false });
+    }
+
+    let ident: Ident = input.parse()?;
+    if ident != "local" {
+      return Err(syn::Error::new(
+        ident.span(),
+        "unknown argument, expected `local`",
+      ));
+    }
+    Ok(Self { local: true })
+  }
+}
+
+pub(crate) fn expand(args: Args, item: ItemStruct) -> syn::Result<TokenStream> {
+  let fields = match &item.fields {
+    Fields::Named(fields) => &fields.named,
+    _ => {
+      return Err(syn::Error::new(
+        item.span(),
+        "`#[shared]` only supports structs with named fields",
+      ))
+    }
+  };
+
+  let (ptr, cost) = if args.local {
+    (
+      quote!(::cheap_clone::__private::Rc),
+      quote!(::cheap_clone::CloneCost::LocalRefCount),
+    )
+  } else {
+    (
+      quote!(::cheap_clone::__private::Arc),
+      quote!(::cheap_clone::CloneCost::AtomicRefCount),
+    )
+  };
+
+  let vis = &item.vis;
+  let name = &item.ident;
+  let inner_name = format_ident!("{}Inner", name);
+  let generics = &item.generics;
+  let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+  // Doc comments document the handle, `cfg` and `allow` apply to every generated item, and
+  // everything else (derives, `repr`, ...) describes the data and stays with the inner struct.
+  let (docs, attrs): (Vec<_>, Vec<_>) = item
+    .attrs
+    .iter()
+    .partition(|attr| attr.path().is_ident("doc"));
+  let (common, attrs): (Vec<_>, Vec<_>) = attrs.into_iter().partition(|attr| {
+    COMMON_ATTRS
+      .iter()
+      .any(|common| attr.path().is_ident(common))
+  });
+
+  let names = fields.iter().map(|f| &f.ident).collect::<Vec<_>>();
+  let tys = fields.iter().map(|f| &f.ty).collect::<Vec<_>>();
+  let accessors = fields.iter().map(|f| {
+    let field_vis = &f.vis;
+    let field_name = &f.ident;
+    let ty = &f.ty;
+    let docs = f.attrs.iter().filter(|attr| attr.path().is_ident("doc"));
+    quote! {
+      #(#docs)*
+      #[allow(dead_code)]
+      #[inline]
+      #field_vis fn #field_name(&self) -> &#ty {
+        &self.inner.#field_name
+      }
+    }
+  });
+  let inner_fields = fields.iter().map(|f| {
+    let mut f = f.clone();
+    f.vis = syn::Visibility::Inherited;
+    f
+  });
+  let ctor_doc = format!("Creates a new `{}`.", name);
+
+  Ok(quote! {
+    #(#common)*
+    #(#attrs)*
+    struct #inner_name #generics #where_clause {
+      #(#inner_fields,)*
+    }
+
+    #(#common)*
+    #(#docs)*
+    #vis struct #name #generics #where_clause {
+      inner: #ptr<#inner_name #ty_generics>,
+    }
+
+    #(#common)*
+    impl #impl_generics ::core::clone::Clone for #name #ty_generics #where_clause {
+      #[inline]
+      fn clone(&self) -> Self {
+        Self {
+          inner: #ptr::clone(&self.inner),
+        }
+      }
+    }
+
+    #(#common)*
+    impl #impl_generics ::cheap_clone::CheapClone for #name #ty_generics #where_clause {
+      const COST: ::cheap_clone::CloneCost = #cost;
+    }
+
+    #(#common)*
+    impl #impl_generics #name #ty_generics #where_clause {
+      #[doc = #ctor_doc]
+      #[inline]
+      #vis fn new(#(#names: #tys),*) -> Self {
+        Self {
+          inner: #ptr::new(#inner_name { #(#names),* }),
+        }
+      }
+
+      #(#accessors)*
+    }
+  })
+}
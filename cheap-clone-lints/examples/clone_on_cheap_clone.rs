@@ -0,0 +1,21 @@
+use std::{rc::Rc, sync::Arc};
+
+use cheap_clone::CheapClone;
+
+fn main() {
+  let shared = Arc::new(String::from("shared"));
+  let local: Rc<str> = Rc::from("local");
+
+  // Flagged: the values are `CheapClone`.
+  let _ = shared.clone();
+  let _ = (local.clone(), Some(shared.clone()));
+
+  // Not flagged.
+  let _ = shared.cheap_clone();
+  let name = String::from("name");
+  let _ = name.clone();
+  let id = 1u32;
+  // Left to `clippy::clone_on_copy`.
+  #[allow(clippy::clone_on_copy)]
+  let _ = id.clone();
+}
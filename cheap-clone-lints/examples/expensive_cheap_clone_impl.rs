@@ -0,0 +1,90 @@
+use std::{collections::HashMap, sync::Arc};
+
+use cheap_clone::CheapClone;
+
+#[derive(Clone)]
+struct Config {
+  peers: Vec<String>,
+}
+
+// Flagged: `Vec` is copied by the default `self.clone()`.
+impl CheapClone for Config {}
+
+#[derive(Clone)]
+struct Routes(u16, HashMap<String, u16>);
+
+// Flagged.
+impl CheapClone for Routes {}
+
+#[derive(Clone)]
+struct Retry {
+  backoff: Option<Vec<u64>>,
+}
+
+// Flagged: the `Vec` inside of the `Option` is copied.
+impl CheapClone for Retry {}
+
+#[derive(Clone)]
+struct Names([String; 4]);
+
+// Flagged: every `String` of the array is copied.
+impl CheapClone for Names {}
+
+#[derive(Clone)]
+struct Frame {
+  payload: (Vec<u8>,),
+}
+
+// Flagged.
+impl CheapClone for Frame {}
+
+#[derive(Clone)]
+struct SharedRoutes {
+  routes: Option<Arc<HashMap<String, u16>>>,
+}
+
+// Not flagged: the `HashMap` is shared behind an `Arc`.
+impl CheapClone for SharedRoutes {}
+
+#[derive(Clone)]
+struct SharedConfig {
+  peers: Arc<[String]>,
+}
+
+// Not flagged: every field is cheap to clone.
+impl CheapClone for SharedConfig {}
+
+#[derive(Clone)]
+struct Cached {
+  name: String,
+}
+
+// Not flagged: `cheap_clone` is implemented explicitly.
+impl CheapClone for Cached {
+  fn cheap_clone(&self) -> Self {
+    Self {
+      name: self.name.clone(),
+    }
+  }
+}
+
+fn main() {
+  let _ = Config { peers: Vec::new() }.peers;
+  let routes = Routes(0, HashMap::new());
+  let _ = (routes.0, routes.1);
+  let _ = Retry { backoff: None }.backoff;
+  let _ = Names(Default::default()).0;
+  let _ = Frame {
+    payload: (Vec::new(),),
+  }
+  .payload;
+  let _ = SharedRoutes { routes: None }.routes;
+  let _ = SharedConfig {
+    peers: Arc::from(Vec::new()),
+  }
+  .peers;
+  let _ = Cached {
+    name: String::new(),
+  }
+  .name;
+}
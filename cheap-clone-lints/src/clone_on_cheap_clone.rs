@@ -0,0 +1,97 @@
+use rustc_errors::Applicability;
+use rustc_hir::{def_id::DefId, Expr, ExprKind};
+use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_session::{declare_lint, impl_lint_pass};
+use rustc_span::sym;
+
+use crate::{cheap_clone_trait, implements_trait};
+
+declare_lint! {
+  /// ### What it does
+  ///
+  /// Checks for `.clone()` calls on values whose type implements `CheapClone`.
+  ///
+  /// ### Why is this bad?
+  ///
+  /// `CheapClone` exists so that the remaining `.clone()` calls are the ones which need to
+  /// be audited for performance. Cloning a cheap value with `.clone()` hides it among them.
+  ///
+  /// `Copy` types are left to `clippy::clone_on_copy`.
+  ///
+  /// ### Example
+  ///
+  /// ```rust,ignore
+  /// let handle = Arc::new(state);
+  /// spawn(handle.clone());
+  /// ```
+  ///
+  /// Use instead:
+  ///
+  /// ```rust,ignore
+  /// use cheap_clone::CheapClone;
+  ///
+  /// let handle = Arc::new(state);
+  /// spawn(handle.cheap_clone());
+  /// ```
+  pub CLONE_ON_CHEAP_CLONE,
+  Warn,
+  "using `.clone()` on a type which implements `CheapClone`"
+}
+
+#[derive(Default)]
+pub struct CloneOnCheapClone {
+  cheap_clone: Option<DefId>,
+}
+
+impl_lint_pass!(CloneOnCheapClone => [CLONE_ON_CHEAP_CLONE]);
+
+impl<'tcx> LateLintPass<'tcx> for CloneOnCheapClone {
+  fn check_crate(&mut self, cx: &LateContext<'tcx>) {
+    self.cheap_clone = cheap_clone_trait(cx.tcx);
+  }
+
+  fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) {
+    let cheap_clone = match self.cheap_clone {
+      Some(cheap_clone) => cheap_clone,
+      None => return,
+    };
+    let method = match expr.kind {
+      ExprKind::MethodCall(method, _, [], _) => method,
+      _ => return,
+    };
+    if method.ident.name != sym::clone || expr.span.from_expansion() {
+      return;
+    }
+
+    let is_clone_trait_method = cx
+      .typeck_results()
+      .type_dependent_def_id(expr.hir_id)
+      .is_some_and(|def_id| cx.tcx.trait_of_item(def_id) == cx.tcx.lang_items().clone_trait());
+    if !is_clone_trait_method {
+      return;
+    }
+
+    let ty = cx.typeck_results().node_args(expr.hir_id).type_at(0);
+    let is_copy = cx
+      .tcx
+      .lang_items()
+      .copy_trait()
+      .is_some_and(|copy| implements_trait(cx, ty, copy));
+    if is_copy || !implements_trait(cx, ty, cheap_clone) {
+      return;
+    }
+
+    cx.span_lint(CLONE_ON_CHEAP_CLONE, method.ident.span, |diag| {
+      diag.primary_message(format!(
+        "using `.clone()` on `{ty}`, which implements `CheapClone`"
+      ));
+      // Only applies if `CheapClone` is in scope.
+      diag.span_suggestion(
+        method.ident.span,
+        "use",
+        "cheap_clone",
+        Applicability::MaybeIncorrect,
+      );
+    });
+  }
+}
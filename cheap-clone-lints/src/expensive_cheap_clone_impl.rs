@@ -0,0 +1,174 @@
+use rustc_hir::{def_id::DefId, Item, ItemKind};
+use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_middle::ty::{self, Ty};
+use rustc_session::{declare_lint, impl_lint_pass};
+use rustc_span::{sym, Symbol};
+
+use crate::{cheap_clone_trait, implements_trait};
+
+declare_lint! {
+  /// ### What it does
+  ///
+  /// Checks for manual `impl CheapClone for X {}` blocks which keep the default
+  /// `cheap_clone` (`self.clone()`), while `X` has a field which is expensive to clone, such
+  /// as a `Vec`, `String`, `HashMap` or `Box`, also inside an `Option`, a tuple, an array or
+  /// another type which is not `CheapClone`.
+  ///
+  /// ### Why is this bad?
+  ///
+  /// Cloning `X` copies the whole collection, so the impl defeats the purpose of
+  /// `CheapClone`.
+  ///
+  /// ### Example
+  ///
+  /// ```rust,ignore
+  /// #[derive(Clone)]
+  /// struct Config {
+  ///   peers: Vec<String>,
+  /// }
+  ///
+  /// impl CheapClone for Config {}
+  /// ```
+  ///
+  /// Use instead:
+  ///
+  /// ```rust,ignore
+  /// #[derive(Clone)]
+  /// struct Config {
+  ///   peers: Arc<[String]>,
+  /// }
+  ///
+  /// impl CheapClone for Config {}
+  /// ```
+  pub EXPENSIVE_CHEAP_CLONE_IMPL,
+  Warn,
+  "`CheapClone` implemented with the default `self.clone()` for a type with expensive fields"
+}
+
+#[derive(Default)]
+pub struct ExpensiveCheapCloneImpl {
+  cheap_clone: Option<DefId>,
+}
+
+impl_lint_pass!(ExpensiveCheapCloneImpl => [EXPENSIVE_CHEAP_CLONE_IMPL]);
+
+impl<'tcx> LateLintPass<'tcx> for ExpensiveCheapCloneImpl {
+  fn check_crate(&mut self, cx: &LateContext<'tcx>) {
+    self.cheap_clone = cheap_clone_trait(cx.tcx);
+  }
+
+  fn check_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx Item<'tcx>) {
+    let cheap_clone = match self.cheap_clone {
+      Some(cheap_clone) => cheap_clone,
+      None => return,
+    };
+    if !matches!(item.kind, ItemKind::Impl(..)) || item.span.from_expansion() {
+      return;
+    }
+
+    let trait_ref = match cx.tcx.impl_trait_ref(item.owner_id) {
+      Some(trait_ref) => trait_ref.instantiate_identity(),
+      None => return,
+    };
+    if trait_ref.def_id != cheap_clone {
+      return;
+    }
+
+    // An explicit `cheap_clone` is trusted.
+    let overrides_cheap_clone = cx
+      .tcx
+      .associated_item_def_ids(item.owner_id)
+      .iter()
+      .any(|&def_id| cx.tcx.item_name(def_id).as_str() == "cheap_clone");
+    if overrides_cheap_clone {
+      return;
+    }
+
+    let self_ty = trait_ref.self_ty();
+    let (adt, args) = match self_ty.kind() {
+      ty::Adt(adt, args) => (adt, args),
+      _ => return,
+    };
+    let expensive = adt.all_fields().find_map(|field| {
+      expensive_kind(cx, field.ty(cx.tcx, args), cheap_clone, 0).map(|kind| (field, kind))
+    });
+    let (field, kind) = match expensive {
+      Some(expensive) => expensive,
+      None => return,
+    };
+
+    cx.span_lint(EXPENSIVE_CHEAP_CLONE_IMPL, item.span, |diag| {
+      diag.primary_message(format!(
+        "`CheapClone` for `{self_ty}` uses the default `self.clone()`, which is expensive"
+      ));
+      diag.span_note(
+        cx.tcx.def_span(field.did),
+        format!(
+          "field `{}` holds a `{kind}`, which is copied on every clone",
+          field.name
+        ),
+      );
+      diag.help("share the field behind an `Arc`, or implement `cheap_clone` explicitly");
+    });
+  }
+}
+
+/// The standard collections whose `Clone` copies all of their elements, besides `String`.
+const EXPENSIVE_COLLECTIONS: &[Symbol] = &[
+  sym::Vec,
+  sym::HashMap,
+  sym::HashSet,
+  sym::BTreeMap,
+  sym::BTreeSet,
+  sym::VecDeque,
+];
+
+/// How deep `expensive_kind` looks into nested fields. Types can only nest through a pointer,
+/// which either ends the search or is expensive itself, so this only bounds unusual types.
+const MAX_DEPTH: usize = 8;
+
+/// Returns the name of the type if cloning a `ty` allocates and copies some contents.
+///
+/// Looks into tuples, arrays and the fields of types which are not `CheapClone`, so e.g.
+/// `Option<Vec<u8>>`, `[String; 4]` and `(Vec<u8>,)` are expensive, but stops at `CheapClone`
+/// types, which share their contents (e.g. `Arc<Vec<u8>>`). Generic parameters are not
+/// known, so they are assumed to be cheap.
+fn expensive_kind<'tcx>(
+  cx: &LateContext<'tcx>,
+  ty: Ty<'tcx>,
+  cheap_clone: DefId,
+  depth: usize,
+) -> Option<Symbol> {
+  if depth > MAX_DEPTH {
+    return None;
+  }
+  match ty.kind() {
+    ty::Tuple(fields) => fields
+      .iter()
+      .find_map(|field| expensive_kind(cx, field, cheap_clone, depth + 1)),
+    ty::Array(elem, _) => expensive_kind(cx, *elem, cheap_clone, depth + 1),
+    ty::Adt(adt, args) => {
+      if ty.is_box() {
+        return Some(Symbol::intern("Box"));
+      }
+      // `String` is a lang item rather than a diagnostic item.
+      if cx.tcx.lang_items().string() == Some(adt.did()) {
+        return Some(sym::String);
+      }
+      if let Some(name) = EXPENSIVE_COLLECTIONS
+        .iter()
+        .copied()
+        .find(|&name| cx.tcx.is_diagnostic_item(name, adt.did()))
+      {
+        return Some(name);
+      }
+      if implements_trait(cx, ty, cheap_clone) {
+        return None;
+      }
+      adt
+        .all_fields()
+        .find_map(|field| expensive_kind(cx, field.ty(cx.tcx, args), cheap_clone, depth + 1))
+    }
+    _ => None,
+  }
+}
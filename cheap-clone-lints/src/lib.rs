@@ -0,0 +1,62 @@
+//! [Dylint](https://github.com/trailofbits/dylint) lints for the
+//! [`cheap-clone`](https://docs.rs/cheap-clone) crate.
+//!
+//! - [`clone_on_cheap_clone`](clone_on_cheap_clone::CLONE_ON_CHEAP_CLONE)
+//! - [`expensive_cheap_clone_impl`](expensive_cheap_clone_impl::EXPENSIVE_CHEAP_CLONE_IMPL)
+#![feature(rustc_private)]
+#![warn(unused_extern_crates)]
+
+extern crate rustc_errors;
+extern crate rustc_hir;
+extern crate rustc_lint;
+extern crate rustc_middle;
+extern crate rustc_session;
+extern crate rustc_span;
+extern crate rustc_trait_selection;
+
+use rustc_hir::def_id::DefId;
+use rustc_lint::LateContext;
+use rustc_middle::ty::{Ty, TyCtxt};
+use rustc_trait_selection::infer::{InferCtxtExt, TyCtxtInferExt};
+
+pub mod clone_on_cheap_clone;
+pub mod expensive_cheap_clone_impl;
+
+dylint_linting::dylint_library!();
+
+#[allow(clippy::no_mangle_with_rust_abi)]
+#[no_mangle]
+pub fn register_lints(sess: &rustc_session::Session, lint_store: &mut rustc_lint::LintStore) {
+  dylint_linting::init_config(sess);
+  lint_store.register_lints(&[
+    clone_on_cheap_clone::CLONE_ON_CHEAP_CLONE,
+    expensive_cheap_clone_impl::EXPENSIVE_CHEAP_CLONE_IMPL,
+  ]);
+  lint_store.register_late_pass(|_| Box::<clone_on_cheap_clone::CloneOnCheapClone>::default());
+  lint_store
+    .register_late_pass(|_| Box::<expensive_cheap_clone_impl::ExpensiveCheapCloneImpl>::default());
+}
+
+/// Returns the `DefId` of `cheap_clone::CheapClone`, or `None` if the linted crate does not
+/// depend on `cheap-clone`.
+fn cheap_clone_trait(tcx: TyCtxt<'_>) -> Option<DefId> {
+  let krate = tcx
+    .crates(())
+    .iter()
+    .copied()
+    .find(|&krate| tcx.crate_name(krate).as_str() == "cheap_clone")?;
+  tcx
+    .traits(krate)
+    .iter()
+    .copied()
+    .find(|&def_id| tcx.item_name(def_id).as_str() == "CheapClone")
+}
+
+/// Returns whether `ty` implements the trait `trait_id`.
+fn implements_trait<'tcx>(cx: &LateContext<'tcx>, ty: Ty<'tcx>, trait_id: DefId) -> bool {
+  let ty = cx.tcx.erase_regions(ty);
+  let infcx = cx.tcx.infer_ctxt().build(cx.typing_mode());
+  infcx
+    .type_implements_trait(trait_id, [ty], cx.param_env)
+    .must_apply_modulo_regions()
+}
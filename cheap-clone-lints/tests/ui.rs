@@ -0,0 +1,4 @@
+#[test]
+fn ui_examples() {
+  dylint_testing::ui_test_examples(env!("CARGO_PKG_NAME"));
+}
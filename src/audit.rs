@@ -0,0 +1,20 @@
+use super::CheapClone;
+
+/// The method `#[audit]` rewrites `.clone()` calls to.
+///
+/// Unlike `Clone`, it is implemented for every type and only requires `CheapClone` on the
+/// method, so method lookup picks the same receiver `.clone()` would have picked for a
+/// `Clone` type, and then reports the missing `CheapClone` impl. E.g. `v.clone()` with
+/// `v: &Vec<u8>` does not silently turn into a clone of the reference.
+pub trait AuditClone {
+  /// Returns a cheap clone of `self`.
+  #[inline(always)]
+  fn __audited_clone(&self) -> Self
+  where
+    Self: CheapClone,
+  {
+    self.cheap_clone()
+  }
+}
+
+impl<T: ?Sized> AuditClone for T {}
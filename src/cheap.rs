@@ -0,0 +1,90 @@
+use super::CheapClone;
+
+/// Clones a value cheaply, or fails to compile.
+///
+/// The value is copied if its type is `Copy`, even if it does not implement [`CheapClone`]
+/// (e.g. a foreign `Copy` type), and cloned with
+/// [`CheapClone::cheap_clone`](crate::CheapClone::cheap_clone) otherwise. Types which are
+/// neither are a compile error. The expression is borrowed, not moved. Like arrays, copied
+/// values must not be larger than [`MAX_ARRAY_BYTES`](crate::MAX_ARRAY_BYTES).
+///
+/// The choice is made at compile time with autoref specialization, so it works on stable
+/// Rust, but only for concrete types: in generic code `T: Copy` is only used if it is a bound
+/// of the function.
+///
+/// ```rust
+/// use cheap_clone::cheap;
+/// # #[cfg(feature = "alloc")]
+/// # {
+/// use std::{sync::Arc, time::Duration};
+///
+/// // `Duration` is `Copy` but not `CheapClone`.
+/// let timeout = Duration::from_secs(1);
+/// let handle = Arc::new(1);
+///
+/// let (a, b) = (cheap!(timeout), cheap!(handle));
+/// assert_eq!(a, timeout);
+/// assert!(Arc::ptr_eq(&b, &handle));
+/// # }
+/// ```
+///
+/// ```rust,compile_fail
+/// let name = String::from("expensive");
+/// let copy = cheap_clone::cheap!(name);
+/// ```
+///
+/// ```rust,compile_fail
+/// let buffer = [0u8; 1 << 16];
+/// let copy = cheap_clone::cheap!(buffer);
+/// ```
+#[macro_export]
+macro_rules! cheap {
+  ($value:expr $(,)?) => {{
+    #[allow(unused_imports)]
+    use $crate::__private::{CheapViaCheapClone as _, CheapViaCopy as _};
+    (&$crate::__private::Cheap(&$value)).__cheap()
+  }};
+}
+
+/// A borrowed value whose `__cheap` method is resolved by [`cheap!`].
+///
+/// Method lookup tries `&Cheap` (taken by [`CheapViaCopy`]) before `&&Cheap` (taken by
+/// [`CheapViaCheapClone`]), so `Copy` wins.
+pub struct Cheap<'a, T>(pub &'a T);
+
+/// Copies the value, if it is `Copy`.
+pub trait CheapViaCopy<T> {
+  /// Returns a copy of the value.
+  fn __cheap(&self) -> T;
+}
+
+impl<T: Copy> CheapViaCopy<T> for Cheap<'_, T> {
+  #[inline(always)]
+  #[allow(clippy::let_unit_value)]
+  fn __cheap(&self) -> T {
+    // A copy is as expensive as copying an array of one `T`.
+    let () = crate::ArrayBudget::<T, 1>::ARRAY_EXCEEDS_MAX_ARRAY_BYTES;
+    *self.0
+  }
+}
+
+/// Clones the value with [`CheapClone::cheap_clone`].
+///
+/// The bound is on the method rather than the impl, so a type which is neither `Copy` nor
+/// `CheapClone` reports the missing `CheapClone` impl instead of a missing method.
+pub trait CheapViaCheapClone<T> {
+  /// Returns a cheap clone of the value.
+  fn __cheap(&self) -> T
+  where
+    T: CheapClone;
+}
+
+impl<T> CheapViaCheapClone<T> for &Cheap<'_, T> {
+  #[inline(always)]
+  fn __cheap(&self) -> T
+  where
+    T: CheapClone,
+  {
+    self.0.cheap_clone()
+  }
+}
@@ -0,0 +1,61 @@
+/// Cheaply clones values into a closure or an `async` block.
+///
+/// Every capture is cloned with [`CheapClone::cheap_clone`](crate::CheapClone::cheap_clone)
+/// before the body is evaluated, so capturing a value which is not `CheapClone` is a
+/// compile error. Captures can be renamed with `as`, and `weak` captures a pointer such as
+/// [`Arc`](alloc::sync::Arc) or [`Rc`](alloc::rc::Rc) as its [`Weak`](crate::Downgrade::Weak)
+/// counterpart.
+///
+/// ```rust
+/// use cheap_clone::clone;
+/// # #[cfg(feature = "alloc")]
+/// # {
+/// use std::sync::Arc;
+///
+/// let counter = Arc::new(1);
+/// let name = Arc::<str>::from("worker");
+/// let id = 7u64;
+///
+/// let task = clone!(counter, name as label, weak counter as observer, id => move || {
+///   assert_eq!(*counter, 1);
+///   assert_eq!(&*label, "worker");
+///   assert!(observer.upgrade().is_some());
+///   id
+/// });
+/// assert_eq!(task(), 7);
+///
+/// // `async move` blocks work the same way.
+/// let fut = clone!(counter => async move { *counter + 1 });
+/// # drop(fut);
+/// # }
+/// ```
+#[macro_export]
+macro_rules! clone {
+  (@capture [$($out:tt)*] => $body:expr) => {{
+    #[allow(unused_imports)]
+    use $crate::CheapClone as _;
+    $($out)*
+    $body
+  }};
+  (@capture [$($out:tt)*] weak $value:ident as $name:ident $($rest:tt)*) => {
+    $crate::clone!(@next [$($out)* let $name = $crate::Downgrade::downgrade(&$value);] $($rest)*)
+  };
+  (@capture [$($out:tt)*] weak $value:ident $($rest:tt)*) => {
+    $crate::clone!(@next [$($out)* let $value = $crate::Downgrade::downgrade(&$value);] $($rest)*)
+  };
+  (@capture [$($out:tt)*] $value:ident as $name:ident $($rest:tt)*) => {
+    $crate::clone!(@next [$($out)* let $name = $value.cheap_clone();] $($rest)*)
+  };
+  (@capture [$($out:tt)*] $value:ident $($rest:tt)*) => {
+    $crate::clone!(@next [$($out)* let $value = $value.cheap_clone();] $($rest)*)
+  };
+  (@next [$($out:tt)*] , $($rest:tt)*) => {
+    $crate::clone!(@capture [$($out)*] $($rest)*)
+  };
+  (@next [$($out:tt)*] => $body:expr) => {
+    $crate::clone!(@capture [$($out)*] => $body)
+  };
+  ($($rest:tt)+) => {
+    $crate::clone!(@capture [] $($rest)+)
+  };
+}
@@ -0,0 +1,216 @@
+//! Property-based checks for [`CheapClone`] implementations.
+//!
+//! [`conformance!`](crate::conformance!) generates a test module running these checks for a
+//! type. They can also be called directly with any [`Strategy`].
+
+/// The version of `proptest` used by the checks, to write strategies with.
+pub use proptest_crate as proptest;
+
+use core::fmt::Debug;
+use std::{thread, vec::Vec};
+
+use proptest_crate::{
+  collection,
+  prelude::{any, prop_assert, prop_assert_eq, Strategy, TestCaseError},
+  sample::Index,
+  test_runner::{Config, TestRunner},
+};
+
+use super::{CheapClone, SharedHandle};
+
+/// How many clones [`check_drop_order`] drops.
+const CLONES: usize = 8;
+
+/// Runs `test` for the values of `strategy`, panicking with the minimal failing value.
+fn run<S: Strategy>(strategy: S, test: impl Fn(S::Value) -> Result<(), TestCaseError>) {
+  // The generated tests have no source file to persist failures next to.
+  let mut runner = TestRunner::new(Config {
+    failure_persistence: None,
+    ..Config::default()
+  });
+  if let Err(err) = runner.run(&strategy, test) {
+    panic!("{}", err);
+  }
+}
+
+/// Checks that `cheap_clone()` is equal (under `eq`) to `clone()` and to the original value.
+pub fn check_clone_eq<T, S>(strategy: S, eq: impl Fn(&T, &T) -> bool)
+where
+  T: CheapClone + Debug,
+  S: Strategy<Value = T>,
+{
+  run(strategy, |value| {
+    let cheap = value.cheap_clone();
+    prop_assert!(
+      eq(&cheap, &value.clone()),
+      "`cheap_clone()` differs from `clone()`"
+    );
+    prop_assert!(
+      eq(&cheap, &value),
+      "`cheap_clone()` differs from the original"
+    );
+    Ok(())
+  })
+}
+
+/// Checks that clones can be dropped in any order, and that the remaining clones stay equal
+/// to the original value.
+pub fn check_drop_order<T, S>(strategy: S, eq: impl Fn(&T, &T) -> bool)
+where
+  T: CheapClone + Debug,
+  S: Strategy<Value = T>,
+{
+  let order = collection::vec(any::<Index>(), CLONES);
+  run((strategy, order), |(value, order)| {
+    let mut clones: Vec<T> = (0..CLONES).map(|_| value.cheap_clone()).collect();
+    for index in order {
+      drop(clones.swap_remove(index.index(clones.len())));
+      for clone in &clones {
+        prop_assert!(
+          eq(clone, &value),
+          "a clone changed after dropping another one"
+        );
+      }
+    }
+    Ok(())
+  })
+}
+
+/// Checks that clones share their storage according to [`SharedHandle::ptr_eq`], and that
+/// the reference counts (if tracked, and the value is alive) go up by one per clone and back down when it is dropped.
+pub fn check_shared<T, S>(strategy: S)
+where
+  T: SharedHandle + Debug,
+  S: Strategy<Value = T>,
+{
+  // A dangling `Weak` reports no handles at all.
+  fn handles<T: SharedHandle>(value: &T) -> Option<usize> {
+    match SharedHandle::strong_count(value)? {
+      0 => None,
+      strong => Some(strong + SharedHandle::weak_count(value)?),
+    }
+  }
+
+  run(strategy, |value| {
+    let before = handles(&value);
+    let clone = value.cheap_clone();
+    prop_assert!(
+      SharedHandle::ptr_eq(&value, &clone),
+      "the clone does not share the storage"
+    );
+    prop_assert_eq!(handles(&value), before.map(|count| count + 1));
+    drop(clone);
+    prop_assert_eq!(handles(&value), before);
+    Ok(())
+  })
+}
+
+/// Checks that clones can be cloned, dropped and sent back on another thread.
+pub fn check_send<T, S>(strategy: S, eq: impl Fn(&T, &T) -> bool)
+where
+  T: CheapClone + Send + Debug + 'static,
+  S: Strategy<Value = T>,
+{
+  run(strategy, |value| {
+    let (a, b) = (value.cheap_clone(), value.cheap_clone());
+    let (b, c) = thread::spawn(move || {
+      let c = a.cheap_clone();
+      drop(a);
+      (b, c)
+    })
+    .join()
+    .map_err(|_| TestCaseError::fail("the thread panicked"))?;
+    prop_assert!(
+      eq(&b, &value) && eq(&c, &value),
+      "a clone changed on another thread"
+    );
+    Ok(())
+  })
+}
+
+/// Generates a test module per type, checking its [`CheapClone`](crate::CheapClone) impl
+/// with the functions of [`conformance`](mod@crate::conformance).
+///
+/// Every module checks [`check_clone_eq`](crate::conformance::check_clone_eq) and
+/// [`check_drop_order`](crate::conformance::check_drop_order). Values are generated with
+/// `proptest::arbitrary::any`, or with the strategy given after `=`. Options:
+///
+/// - `shared`: also [`check_shared`](crate::conformance::check_shared), the type must
+///   implement [`SharedHandle`](crate::SharedHandle).
+/// - `send`: also [`check_send`](crate::conformance::check_send).
+/// - `eq = expr`: compares values with `expr: Fn(&T, &T) -> bool` instead of `PartialEq`.
+///
+/// ```rust,ignore
+/// use std::sync::Arc;
+/// use cheap_clone::conformance::proptest::prelude::*;
+///
+/// cheap_clone::conformance! {
+///   arc: Arc<u8>, shared, send;
+///   config: MyConfig = any::<String>().prop_map(MyConfig::new), send;
+/// }
+/// ```
+#[macro_export]
+macro_rules! conformance {
+  ($($name:ident: $ty:ty $(= $strategy:expr)? $(, $option:ident $(= $value:expr)?)*);+ $(;)?) => {
+    $(
+      mod $name {
+        #[allow(unused_imports)]
+        use super::*;
+
+        $crate::__conformance!(
+          @parse $ty;
+          [$crate::__conformance!(@strategy $ty $(, $strategy)?)];
+          [<$ty as ::core::cmp::PartialEq>::eq];
+          [];
+          $($option $(= $value)?),*
+        );
+      }
+    )+
+  };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __conformance {
+  (@strategy $ty:ty) => {
+    $crate::conformance::proptest::arbitrary::any::<$ty>()
+  };
+  (@strategy $ty:ty, $strategy:expr) => {
+    $strategy
+  };
+  (@parse $ty:ty; $strategy:tt; $eq:tt; [$($flag:ident)*];) => {
+    $crate::__conformance!(@test clone_eq $ty; $strategy; $eq);
+    $crate::__conformance!(@test drop_order $ty; $strategy; $eq);
+    $($crate::__conformance!(@test $flag $ty; $strategy; $eq);)*
+  };
+  (@parse $ty:ty; $strategy:tt; $eq:tt; [$($flag:ident)*]; eq = $value:expr $(, $($rest:tt)*)?) => {
+    $crate::__conformance!(@parse $ty; $strategy; [$value]; [$($flag)*]; $($($rest)*)?);
+  };
+  (@parse $ty:ty; $strategy:tt; $eq:tt; [$($flag:ident)*]; $option:ident $(, $($rest:tt)*)?) => {
+    $crate::__conformance!(@parse $ty; $strategy; $eq; [$($flag)* $option]; $($($rest)*)?);
+  };
+  (@test clone_eq $ty:ty; [$strategy:expr]; [$eq:expr]) => {
+    #[test]
+    fn cheap_clone_eq_clone() {
+      $crate::conformance::check_clone_eq::<$ty, _>($strategy, $eq);
+    }
+  };
+  (@test drop_order $ty:ty; [$strategy:expr]; [$eq:expr]) => {
+    #[test]
+    fn drop_clones_in_any_order() {
+      $crate::conformance::check_drop_order::<$ty, _>($strategy, $eq);
+    }
+  };
+  (@test shared $ty:ty; [$strategy:expr]; [$eq:expr]) => {
+    #[test]
+    fn clones_share_storage() {
+      $crate::conformance::check_shared::<$ty, _>($strategy);
+    }
+  };
+  (@test send $ty:ty; [$strategy:expr]; [$eq:expr]) => {
+    #[test]
+    fn clones_across_threads() {
+      $crate::conformance::check_send::<$ty, _>($strategy, $eq);
+    }
+  };
+}
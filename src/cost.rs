@@ -0,0 +1,39 @@
+/// How expensive [`cheap_clone`](crate::CheapClone::cheap_clone) is for a type, see
+/// [`CheapClone::COST`](crate::CheapClone::COST).
+///
+/// Variants are ordered from cheapest to most expensive, so composite types (tuples, `Option`,
+/// `Result`, ...) report the [`max`](CloneCost::max) of their parts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CloneCost {
+  /// A bitwise copy, e.g. integers, `&T` or `Ipv4Addr`.
+  Copy,
+  /// A non-atomic reference count increment, e.g. [`Rc`](alloc::rc::Rc).
+  LocalRefCount,
+  /// An atomic reference count increment, e.g. [`Arc`](alloc::sync::Arc).
+  AtomicRefCount,
+  /// Any other constant-time work, or a cost which is not known statically, e.g. the vtable
+  /// dispatch of [`Bytes`](bytes::Bytes). This is the default of `CheapClone::COST`.
+  Composite,
+}
+
+impl CloneCost {
+  /// Returns the more expensive of `self` and `other`.
+  #[inline]
+  pub const fn max(self, other: Self) -> Self {
+    if self as u8 >= other as u8 {
+      self
+    } else {
+      other
+    }
+  }
+
+  /// Returns `true` if `self` is at most as expensive as `other`.
+  ///
+  /// Unlike `<=`, this can be used in const contexts.
+  #[inline]
+  pub const fn is_at_most(self, other: Self) -> bool {
+    self as u8 <= other as u8
+  }
+}
+
+crate::impl_cheap_clone!(copy: CloneCost);
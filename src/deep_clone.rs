@@ -0,0 +1,157 @@
+/// An explicit, possibly expensive clone which does not share anything with the original.
+///
+/// This is the counterpart of [`CheapClone`](crate::CheapClone): `cheap_clone` shares the
+/// storage, `deep_clone` copies it into a new allocation. E.g. the deep clone of an
+/// [`Arc<T>`](alloc::sync::Arc) is a new `Arc` holding a clone of `T`, and the deep clone of a
+/// [`Bytes`](bytes::Bytes) is a new buffer holding only its bytes, which does not keep the
+/// (possibly much larger) parent buffer alive.
+///
+/// `Option`, `Result`, tuples and `Either` deep clone their elements. Arrays are not
+/// implemented: building an array element by element needs `core::array::from_fn`, which is
+/// newer than the MSRV of the crate.
+///
+/// ```rust
+/// use cheap_clone::DeepClone;
+/// # #[cfg(feature = "alloc")]
+/// # {
+/// use std::sync::Arc;
+///
+/// let config = Arc::new(String::from("config"));
+/// let copy = (config.deep_clone(), Some(1u8)).deep_clone();
+/// assert_eq!(copy.0, config);
+/// assert!(!Arc::ptr_eq(&copy.0, &config));
+/// # }
+/// ```
+pub trait DeepClone {
+  /// Returns a copy of `self` which does not share any storage with it.
+  fn deep_clone(&self) -> Self;
+}
+
+macro_rules! impl_deep_clone_for_copy {
+  ($($ty:ty),+ $(,)?) => {
+    $(
+      impl DeepClone for $ty {
+        #[inline]
+        fn deep_clone(&self) -> Self {
+          *self
+        }
+      }
+    )*
+  };
+}
+
+impl_deep_clone_for_copy! {
+  (),
+  bool, char, f32, f64, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize,
+  core::num::NonZeroI8,
+  core::num::NonZeroI16,
+  core::num::NonZeroI32,
+  core::num::NonZeroI64,
+  core::num::NonZeroI128,
+  core::num::NonZeroIsize,
+  core::num::NonZeroU8,
+  core::num::NonZeroU16,
+  core::num::NonZeroU32,
+  core::num::NonZeroU64,
+  core::num::NonZeroU128,
+  core::num::NonZeroUsize,
+}
+
+// `T::Owned` is `T` for sized values, `String` for `str` and `Vec<T>` for `[T]`, so one impl
+// covers e.g. `Arc<Config>`, `Arc<str>` and `Arc<[u8]>`.
+#[cfg(feature = "alloc")]
+macro_rules! impl_deep_clone_for_pointer {
+  ($($ptr:ident)::+) => {
+    impl<T> DeepClone for $($ptr)::+<T>
+    where
+      T: ?Sized + alloc::borrow::ToOwned,
+      $($ptr)::+<T>: From<T::Owned>,
+    {
+      #[inline]
+      fn deep_clone(&self) -> Self {
+        Self::from(T::to_owned(self))
+      }
+    }
+  };
+}
+
+#[cfg(feature = "alloc")]
+impl_deep_clone_for_pointer!(alloc::boxed::Box);
+#[cfg(feature = "alloc")]
+impl_deep_clone_for_pointer!(alloc::rc::Rc);
+#[cfg(all(feature = "alloc", not(cheap_clone_no_atomic_ptr)))]
+impl_deep_clone_for_pointer!(alloc::sync::Arc);
+#[cfg(feature = "portable-atomic")]
+impl_deep_clone_for_pointer!(portable_atomic_util::Arc);
+
+#[cfg(all(feature = "alloc", not(cheap_clone_no_atomic_ptr)))]
+impl<T> DeepClone for crate::SharedBox<T>
+where
+  T: ?Sized + alloc::borrow::ToOwned,
+  alloc::sync::Arc<T>: From<T::Owned>,
+{
+  #[inline]
+  fn deep_clone(&self) -> Self {
+    Self::from(alloc::sync::Arc::from(T::to_owned(self)))
+  }
+}
+
+#[cfg(feature = "bytes")]
+impl DeepClone for bytes::Bytes {
+  #[inline]
+  fn deep_clone(&self) -> Self {
+    Self::copy_from_slice(self)
+  }
+}
+
+/// Short strings are stored inline, longer ones are copied into a new allocation.
+#[cfg(feature = "smol_str")]
+impl DeepClone for smol_str::SmolStr {
+  #[inline]
+  fn deep_clone(&self) -> Self {
+    Self::new(self.as_str())
+  }
+}
+
+impl<T: DeepClone> DeepClone for Option<T> {
+  #[inline]
+  fn deep_clone(&self) -> Self {
+    self.as_ref().map(T::deep_clone)
+  }
+}
+
+impl<T: DeepClone, E: DeepClone> DeepClone for Result<T, E> {
+  #[inline]
+  fn deep_clone(&self) -> Self {
+    match self {
+      Ok(value) => Ok(value.deep_clone()),
+      Err(err) => Err(err.deep_clone()),
+    }
+  }
+}
+
+#[cfg(feature = "either")]
+impl<L: DeepClone, R: DeepClone> DeepClone for either::Either<L, R> {
+  #[inline]
+  fn deep_clone(&self) -> Self {
+    match self {
+      either::Either::Left(left) => either::Either::Left(left.deep_clone()),
+      either::Either::Right(right) => either::Either::Right(right.deep_clone()),
+    }
+  }
+}
+
+macro_rules! impl_deep_clone_for_tuple {
+  ($($param:literal),+ $(,)?) => {
+    paste::paste! {
+      impl<$([< T $param >]: DeepClone),+> DeepClone for ($([< T $param >],)+) {
+        #[inline]
+        fn deep_clone(&self) -> Self {
+          ($(self.$param.deep_clone(),)+)
+        }
+      }
+    }
+  };
+}
+
+for_each_tuple!(impl_deep_clone_for_tuple);
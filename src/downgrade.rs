@@ -0,0 +1,100 @@
+use super::CheapClone;
+
+/// Shared pointers which can be downgraded to a weak handle, which does not keep the value
+/// alive.
+pub trait Downgrade: CheapClone {
+  /// The weak counterpart of the pointer.
+  type Weak: CheapClone;
+
+  /// Creates a weak handle to the value behind `this`.
+  fn downgrade(this: &Self) -> Self::Weak;
+
+  /// Returns a strong pointer to the value, or `None` if it has already been dropped.
+  fn upgrade(weak: &Self::Weak) -> Option<Self>;
+}
+
+#[cfg(all(feature = "alloc", not(feature = "nightly-allocator-api")))]
+mod a {
+  use super::Downgrade;
+  use alloc::rc;
+
+  impl<T: ?Sized> Downgrade for rc::Rc<T> {
+    type Weak = rc::Weak<T>;
+
+    #[inline]
+    fn downgrade(this: &Self) -> Self::Weak {
+      rc::Rc::downgrade(this)
+    }
+
+    #[inline]
+    fn upgrade(weak: &Self::Weak) -> Option<Self> {
+      weak.upgrade()
+    }
+  }
+
+  #[cfg(not(cheap_clone_no_atomic_ptr))]
+  impl<T: ?Sized> Downgrade for alloc::sync::Arc<T> {
+    type Weak = alloc::sync::Weak<T>;
+
+    #[inline]
+    fn downgrade(this: &Self) -> Self::Weak {
+      alloc::sync::Arc::downgrade(this)
+    }
+
+    #[inline]
+    fn upgrade(weak: &Self::Weak) -> Option<Self> {
+      weak.upgrade()
+    }
+  }
+}
+
+#[cfg(feature = "nightly-allocator-api")]
+mod allocator_api {
+  use super::Downgrade;
+  use crate::CheapAllocator;
+  use alloc::rc;
+
+  impl<T: ?Sized, A: CheapAllocator> Downgrade for rc::Rc<T, A> {
+    type Weak = rc::Weak<T, A>;
+
+    #[inline]
+    fn downgrade(this: &Self) -> Self::Weak {
+      rc::Rc::downgrade(this)
+    }
+
+    #[inline]
+    fn upgrade(weak: &Self::Weak) -> Option<Self> {
+      weak.upgrade()
+    }
+  }
+
+  #[cfg(not(cheap_clone_no_atomic_ptr))]
+  impl<T: ?Sized, A: CheapAllocator> Downgrade for alloc::sync::Arc<T, A> {
+    type Weak = alloc::sync::Weak<T, A>;
+
+    #[inline]
+    fn downgrade(this: &Self) -> Self::Weak {
+      alloc::sync::Arc::downgrade(this)
+    }
+
+    #[inline]
+    fn upgrade(weak: &Self::Weak) -> Option<Self> {
+      weak.upgrade()
+    }
+  }
+}
+
+#[cfg(feature = "portable-atomic")]
+impl<T: ?Sized> Downgrade for portable_atomic_util::Arc<T> {
+  type Weak = portable_atomic_util::Weak<T>;
+
+  #[inline]
+  fn downgrade(this: &Self) -> Self::Weak {
+    portable_atomic_util::Arc::downgrade(this)
+  }
+
+  #[inline]
+  fn upgrade(weak: &Self::Weak) -> Option<Self> {
+    weak.upgrade()
+  }
+}
@@ -0,0 +1,116 @@
+use alloc::boxed::Box;
+
+use super::CheapClone;
+
+mod sealed {
+  pub trait Sealed {}
+  impl<T: super::CheapClone> Sealed for T {}
+
+  pub struct Private;
+}
+
+use sealed::{Private, Sealed};
+
+/// An object safe version of [`CheapClone`].
+///
+/// Every `CheapClone` type implements `DynCheapClone`. Use it as a supertrait of your own
+/// trait and invoke [`cheap_clone_trait_object!`](crate::cheap_clone_trait_object) to make
+/// `Box<dyn Trait>` implement `Clone` and `CheapClone`.
+///
+/// [`Arc<dyn Trait>`](alloc::sync::Arc) and [`Rc<dyn Trait>`](alloc::rc::Rc) are already
+/// `CheapClone` and do not need this trait.
+///
+/// ```rust
+/// use cheap_clone::{cheap_clone_trait_object, CheapClone, DynCheapClone};
+///
+/// trait Handler: DynCheapClone {
+///   fn handle(&self) -> u32;
+/// }
+///
+/// cheap_clone_trait_object!(Handler);
+///
+/// #[derive(Clone)]
+/// struct Answer(u32);
+///
+/// impl CheapClone for Answer {}
+///
+/// impl Handler for Answer {
+///   fn handle(&self) -> u32 {
+///     self.0
+///   }
+/// }
+///
+/// let handler: Box<dyn Handler> = Box::new(Answer(42));
+/// assert_eq!(handler.clone().handle(), 42);
+/// ```
+pub trait DynCheapClone: Sealed {
+  #[doc(hidden)]
+  fn __cheap_clone_box(&self, _: Private) -> *mut ();
+}
+
+impl<T: CheapClone> DynCheapClone for T {
+  fn __cheap_clone_box(&self, _: Private) -> *mut () {
+    Box::into_raw(Box::new(self.cheap_clone())) as *mut ()
+  }
+}
+
+/// Cheaply clones the value behind a (possibly unsized) reference into a new [`Box`].
+pub fn clone_box<T: ?Sized + DynCheapClone>(t: &T) -> Box<T> {
+  // Keep the metadata (vtable) of `t` and only replace the data pointer.
+  let mut fat_ptr = t as *const T;
+  // SAFETY: the data pointer is the first word of a (fat) pointer, which the assertion checks,
+  // and `__cheap_clone_box` returns a pointer to a `Box` allocation of the same concrete type.
+  unsafe {
+    let data_ptr = &mut fat_ptr as *mut *const T as *mut *mut ();
+    assert_eq!(*data_ptr as *const (), t as *const T as *const ());
+    *data_ptr = <T as DynCheapClone>::__cheap_clone_box(t, Private);
+    Box::from_raw(fat_ptr as *mut T)
+  }
+}
+
+/// Implements `Clone` and [`CheapClone`] for `Box<dyn Trait>` (and its `Send`/`Sync`
+/// variants), where `Trait: DynCheapClone`.
+///
+/// Every clone allocates a new box, so with the `strict` feature only `Clone` is implemented.
+#[macro_export]
+macro_rules! cheap_clone_trait_object {
+  ($($path:tt)+) => {
+    $crate::__cheap_clone_trait_object!($($path)+);
+    $crate::__cheap_clone_trait_object!($($path)+ + ::core::marker::Send);
+    $crate::__cheap_clone_trait_object!($($path)+ + ::core::marker::Sync);
+    $crate::__cheap_clone_trait_object!($($path)+ + ::core::marker::Send + ::core::marker::Sync);
+  };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __cheap_clone_trait_object {
+  ($($bounds:tt)+) => {
+    impl<'clone> ::core::clone::Clone for $crate::__private::Box<dyn $($bounds)+ + 'clone> {
+      fn clone(&self) -> Self {
+        $crate::clone_box(&**self)
+      }
+    }
+
+    $crate::__cheap_clone_box_trait_object!($($bounds)+);
+  };
+}
+
+#[cfg(not(feature = "strict"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __cheap_clone_box_trait_object {
+  ($($bounds:tt)+) => {
+    impl<'clone> $crate::CheapClone for $crate::__private::Box<dyn $($bounds)+ + 'clone> {
+      const COST: $crate::CloneCost = $crate::CloneCost::Composite;
+    }
+  };
+}
+
+// Cloning a `Box<dyn Trait>` allocates, so it is only `Clone` with the `strict` feature.
+#[cfg(feature = "strict")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __cheap_clone_box_trait_object {
+  ($($bounds:tt)+) => {};
+}
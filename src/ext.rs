@@ -0,0 +1,186 @@
+use core::iter::FusedIterator;
+
+use super::CheapClone;
+
+/// Extension methods for every [`CheapClone`] type.
+pub trait CheapCloneExt: CheapClone {
+  /// Returns `N` cheap clones of `self`, e.g. to fan a handle out to a pool of workers.
+  ///
+  /// ```rust
+  /// use cheap_clone::CheapCloneExt;
+  /// # #[cfg(feature = "alloc")]
+  /// # {
+  /// use std::sync::Arc;
+  ///
+  /// let config = Arc::new("config");
+  /// let [a, b, c] = config.cheap_clone_n::<3>();
+  /// assert_eq!(Arc::strong_count(&config), 4);
+  /// # drop((a, b, c));
+  /// # }
+  /// ```
+  #[inline]
+  fn cheap_clone_n<const N: usize>(&self) -> [Self; N] {
+    [(); N].map(|_| self.cheap_clone())
+  }
+}
+
+impl<T: CheapClone> CheapCloneExt for T {}
+
+/// Extension methods for iterators over references to [`CheapClone`] values.
+pub trait CheapIteratorExt: Iterator {
+  /// Cheaply clones every item, the [`CheapClone`] counterpart of
+  /// [`Iterator::cloned`].
+  ///
+  /// ```rust
+  /// use cheap_clone::CheapIteratorExt;
+  /// # #[cfg(feature = "alloc")]
+  /// # {
+  /// use std::rc::Rc;
+  ///
+  /// let names = [Rc::<str>::from("a"), Rc::from("b")];
+  /// let copies: Vec<Rc<str>> = names.iter().cheap_cloned().collect();
+  /// assert!(Rc::ptr_eq(&copies[0], &names[0]));
+  /// # }
+  /// ```
+  #[inline]
+  fn cheap_cloned<'a, T>(self) -> CheapCloned<Self>
+  where
+    Self: Sized + Iterator<Item = &'a T>,
+    T: CheapClone + 'a,
+  {
+    CheapCloned { iter: self }
+  }
+}
+
+impl<I: Iterator> CheapIteratorExt for I {}
+
+/// An iterator which cheaply clones the items of another iterator.
+///
+/// Created by [`CheapIteratorExt::cheap_cloned`].
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct CheapCloned<I> {
+  iter: I,
+}
+
+impl<'a, I, T> Iterator for CheapCloned<I>
+where
+  I: Iterator<Item = &'a T>,
+  T: CheapClone + 'a,
+{
+  type Item = T;
+
+  #[inline]
+  fn next(&mut self) -> Option<T> {
+    self.iter.next().map(T::cheap_clone)
+  }
+
+  #[inline]
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.iter.size_hint()
+  }
+
+  #[inline]
+  fn nth(&mut self, n: usize) -> Option<T> {
+    self.iter.nth(n).map(T::cheap_clone)
+  }
+}
+
+impl<'a, I, T> DoubleEndedIterator for CheapCloned<I>
+where
+  I: DoubleEndedIterator<Item = &'a T>,
+  T: CheapClone + 'a,
+{
+  #[inline]
+  fn next_back(&mut self) -> Option<T> {
+    self.iter.next_back().map(T::cheap_clone)
+  }
+}
+
+impl<'a, I, T> ExactSizeIterator for CheapCloned<I>
+where
+  I: ExactSizeIterator<Item = &'a T>,
+  T: CheapClone + 'a,
+{
+  #[inline]
+  fn len(&self) -> usize {
+    self.iter.len()
+  }
+}
+
+impl<'a, I, T> FusedIterator for CheapCloned<I>
+where
+  I: FusedIterator<Item = &'a T>,
+  T: CheapClone + 'a,
+{
+}
+
+/// Extension methods for `Option<&T>` where `T` is [`CheapClone`].
+pub trait CheapOptionExt<T> {
+  /// Cheaply clones the value, the [`CheapClone`] counterpart of [`Option::cloned`].
+  fn cheap_cloned(self) -> Option<T>;
+}
+
+impl<T: CheapClone> CheapOptionExt<T> for Option<&T> {
+  #[inline]
+  fn cheap_cloned(self) -> Option<T> {
+    self.map(T::cheap_clone)
+  }
+}
+
+/// Returns an endless iterator of cheap clones of `value`, the [`CheapClone`] counterpart of
+/// [`core::iter::repeat`].
+///
+/// ```rust
+/// use cheap_clone::repeat_cheap;
+/// # #[cfg(feature = "alloc")]
+/// # {
+/// use std::sync::Arc;
+///
+/// let jobs = Arc::new(vec![1, 2, 3]);
+/// let workers: Vec<_> = (0..4).zip(repeat_cheap(jobs.clone())).collect();
+/// assert_eq!(Arc::strong_count(&jobs), 5);
+/// # drop(workers);
+/// # }
+/// ```
+#[inline]
+pub fn repeat_cheap<T: CheapClone>(value: T) -> RepeatCheap<T> {
+  RepeatCheap { value }
+}
+
+/// An endless iterator of cheap clones of a value.
+///
+/// Created by [`repeat_cheap`].
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct RepeatCheap<T> {
+  value: T,
+}
+
+impl<T: CheapClone> Iterator for RepeatCheap<T> {
+  type Item = T;
+
+  #[inline]
+  fn next(&mut self) -> Option<T> {
+    Some(self.value.cheap_clone())
+  }
+
+  #[inline]
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    (usize::MAX, None)
+  }
+
+  #[inline]
+  fn nth(&mut self, _: usize) -> Option<T> {
+    Some(self.value.cheap_clone())
+  }
+}
+
+impl<T: CheapClone> DoubleEndedIterator for RepeatCheap<T> {
+  #[inline]
+  fn next_back(&mut self) -> Option<T> {
+    Some(self.value.cheap_clone())
+  }
+}
+
+impl<T: CheapClone> FusedIterator for RepeatCheap<T> {}
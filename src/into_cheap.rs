@@ -0,0 +1,199 @@
+use super::CheapClone;
+
+/// Converts an owned value into a form which is cheap to clone.
+///
+/// | Value | Cheap form |
+/// |-------|------------|
+/// | `String` | `Arc<str>` |
+/// | `Vec<T>` | `Arc<[T]>` |
+/// | `Box<T>` (including `Box<str>` and `Box<[T]>`) | `Arc<T>` |
+/// | `&str` | `SmolStr` (`smol_str` feature) |
+/// | [`ByteVec`] (a `Vec<u8>`, with `alloc`), `BytesMut` | `Bytes` (`bytes` feature) |
+/// | `Arc<T>`, `Rc<T>`, `Bytes`, `SmolStr` | themselves |
+/// | `Option<T>`, tuples | their elements converted |
+///
+/// A plain `Vec<u8>` becomes an `Arc<[u8]>` like every other `Vec<T>` (an impl to `Bytes`
+/// would overlap with the one for `Vec<T>`), wrap it in a [`ByteVec`] to convert it into a
+/// `Bytes` instead.
+///
+/// ```rust
+/// use cheap_clone::{CheapClone, IntoCheap};
+/// # #[cfg(feature = "alloc")]
+/// # {
+/// use std::sync::Arc;
+///
+/// fn register(name: impl IntoCheap<Cheap = Arc<str>>) -> Arc<str> {
+///   name.into_cheap()
+/// }
+///
+/// let name = register(String::from("worker"));
+/// assert_eq!(&*register(name.cheap_clone()), "worker");
+///
+/// let (tags, id) = (vec![1u8, 2], Some(String::from("id"))).into_cheap();
+/// assert_eq!(&*tags, [1, 2]);
+/// assert_eq!(id.as_deref(), Some("id"));
+/// # }
+/// ```
+pub trait IntoCheap {
+  /// The cheaply cloned form of the value.
+  type Cheap: CheapClone;
+
+  /// Converts `self` into its cheaply cloned form.
+  fn into_cheap(self) -> Self::Cheap;
+}
+
+// Values which already are in their cheap form.
+#[cfg(any(feature = "alloc", feature = "bytes", feature = "smol_str"))]
+macro_rules! impl_into_cheap_for_self {
+  ($ty:ty $(, $generic:ident)?) => {
+    impl$(<$generic: ?Sized>)? IntoCheap for $ty {
+      type Cheap = Self;
+
+      #[inline]
+      fn into_cheap(self) -> Self {
+        self
+      }
+    }
+  };
+}
+
+#[cfg(all(feature = "alloc", not(cheap_clone_no_atomic_ptr)))]
+mod a {
+  use super::IntoCheap;
+  use alloc::{boxed::Box, string::String, sync::Arc, vec::Vec};
+
+  impl_into_cheap_for_self!(Arc<T>, T);
+
+  impl IntoCheap for String {
+    type Cheap = Arc<str>;
+
+    #[inline]
+    fn into_cheap(self) -> Arc<str> {
+      Arc::from(self)
+    }
+  }
+
+  impl<T> IntoCheap for Vec<T> {
+    type Cheap = Arc<[T]>;
+
+    #[inline]
+    fn into_cheap(self) -> Arc<[T]> {
+      Arc::from(self)
+    }
+  }
+
+  impl<T: ?Sized> IntoCheap for Box<T> {
+    type Cheap = Arc<T>;
+
+    #[inline]
+    fn into_cheap(self) -> Arc<T> {
+      Arc::from(self)
+    }
+  }
+}
+
+#[cfg(feature = "alloc")]
+impl_into_cheap_for_self!(alloc::rc::Rc<T>, T);
+
+#[cfg(feature = "bytes")]
+impl_into_cheap_for_self!(bytes::Bytes);
+
+#[cfg(feature = "bytes")]
+impl IntoCheap for bytes::BytesMut {
+  type Cheap = bytes::Bytes;
+
+  #[inline]
+  fn into_cheap(self) -> bytes::Bytes {
+    self.freeze()
+  }
+}
+
+/// A `Vec<u8>` which [`IntoCheap`] converts into a [`Bytes`](bytes::Bytes), without copying.
+///
+/// ```rust
+/// use cheap_clone::{ByteVec, IntoCheap};
+/// use bytes::Bytes;
+///
+/// fn send(payload: impl IntoCheap<Cheap = Bytes>) -> Bytes {
+///   payload.into_cheap()
+/// }
+///
+/// assert_eq!(send(ByteVec::new(vec![1, 2])), [1, 2][..]);
+/// assert_eq!(send(Bytes::from_static(b"static")), b"static"[..]);
+/// ```
+#[cfg(all(feature = "bytes", feature = "alloc"))]
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct ByteVec(alloc::vec::Vec<u8>);
+
+#[cfg(all(feature = "bytes", feature = "alloc"))]
+impl ByteVec {
+  /// Wraps `vec` in a `ByteVec`.
+  #[inline]
+  pub const fn new(vec: alloc::vec::Vec<u8>) -> Self {
+    Self(vec)
+  }
+
+  /// Returns the wrapped `Vec<u8>`.
+  #[inline]
+  pub fn into_inner(self) -> alloc::vec::Vec<u8> {
+    self.0
+  }
+}
+
+#[cfg(all(feature = "bytes", feature = "alloc"))]
+impl From<alloc::vec::Vec<u8>> for ByteVec {
+  #[inline]
+  fn from(vec: alloc::vec::Vec<u8>) -> Self {
+    Self(vec)
+  }
+}
+
+#[cfg(all(feature = "bytes", feature = "alloc"))]
+impl IntoCheap for ByteVec {
+  type Cheap = bytes::Bytes;
+
+  #[inline]
+  fn into_cheap(self) -> bytes::Bytes {
+    bytes::Bytes::from(self.0)
+  }
+}
+
+#[cfg(feature = "smol_str")]
+impl_into_cheap_for_self!(smol_str::SmolStr);
+
+#[cfg(feature = "smol_str")]
+impl IntoCheap for &str {
+  type Cheap = smol_str::SmolStr;
+
+  #[inline]
+  fn into_cheap(self) -> smol_str::SmolStr {
+    smol_str::SmolStr::new(self)
+  }
+}
+
+impl<T: IntoCheap> IntoCheap for Option<T> {
+  type Cheap = Option<T::Cheap>;
+
+  #[inline]
+  fn into_cheap(self) -> Self::Cheap {
+    self.map(T::into_cheap)
+  }
+}
+
+macro_rules! impl_into_cheap_for_tuple {
+  ($($param:literal),+ $(,)?) => {
+    paste::paste! {
+      impl<$([< T $param >]: IntoCheap),+> IntoCheap for ($([< T $param >],)+) {
+        type Cheap = ($([< T $param >]::Cheap,)+);
+
+        #[inline]
+        fn into_cheap(self) -> Self::Cheap {
+          ($(self.$param.into_cheap(),)+)
+        }
+      }
+    }
+  };
+}
+
+for_each_tuple!(impl_into_cheap_for_tuple);
@@ -1,10 +1,15 @@
 //! A trait which indicates that such type can be cloned cheaply.
+//!
+//! Like the inherent methods of `Arc` and `Rc`, the functions of the pointer traits
+//! ([`Downgrade`], [`SharedHandle`], [`Unshare`] and [`MakeMut`]) take the pointer as an explicit
+//! argument, e.g. `Downgrade::downgrade(&ptr)`, so they never shadow methods of the pointee.
 #![cfg_attr(not(feature = "std"), no_std)]
 #![cfg_attr(docsrs, feature(doc_cfg))]
+#![cfg_attr(feature = "nightly-allocator-api", feature(allocator_api))]
 #![cfg_attr(docsrs, allow(unused_attributes))]
 #![deny(missing_docs)]
 
-/**
+/*
  * `CheapClone` trait is inspired by https://github.com/graphprotocol/graph-node/blob/master/graph/src/cheap_clone.rs
  */
 
@@ -14,16 +19,151 @@ extern crate alloc;
 #[cfg(feature = "std")]
 extern crate std;
 
-macro_rules! impl_cheap_clone_for_copy {
-  ($($ty: ty), +$(,)?) => {
+/// Invokes `$mac!(0, 1, ..)` with the field indices of every tuple of up to 24 elements.
+macro_rules! for_each_tuple {
+  ($mac:ident) => {
+    $mac!(0);
+    $mac!(0, 1);
+    $mac!(0, 1, 2);
+    $mac!(0, 1, 2, 3);
+    $mac!(0, 1, 2, 3, 4);
+    $mac!(0, 1, 2, 3, 4, 5);
+    $mac!(0, 1, 2, 3, 4, 5, 6);
+    $mac!(0, 1, 2, 3, 4, 5, 6, 7);
+    $mac!(0, 1, 2, 3, 4, 5, 6, 7, 8);
+    $mac!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9);
+    $mac!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10);
+    $mac!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11);
+    $mac!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12);
+    $mac!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13);
+    $mac!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14);
+    $mac!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15);
+    $mac!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16);
+    $mac!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17);
+    $mac!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18);
+    $mac!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19);
+    $mac!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20);
+    $mac!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21);
+    $mac!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22);
+    $mac!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23);
+  };
+}
+
+#[cfg(feature = "derive")]
+mod audit;
+mod cheap;
+mod clone;
+
+#[cfg(feature = "testing")]
+#[cfg_attr(docsrs, doc(cfg(feature = "testing")))]
+pub mod testing;
+
+#[cfg(feature = "proptest")]
+#[cfg_attr(docsrs, doc(cfg(feature = "proptest")))]
+pub mod conformance;
+
+mod cost;
+pub use cost::CloneCost;
+
+mod deep_clone;
+pub use deep_clone::DeepClone;
+
+mod downgrade;
+pub use downgrade::Downgrade;
+
+mod ext;
+pub use ext::{
+  repeat_cheap, CheapCloneExt, CheapCloned, CheapIteratorExt, CheapOptionExt, RepeatCheap,
+};
+
+mod into_cheap;
+#[cfg(all(feature = "bytes", feature = "alloc"))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "bytes", feature = "alloc"))))]
+pub use into_cheap::ByteVec;
+pub use into_cheap::IntoCheap;
+
+mod make_mut;
+pub use make_mut::{MakeMut, Unshare};
+
+mod shared_handle;
+pub use shared_handle::SharedHandle;
+
+#[cfg(feature = "nightly-allocator-api")]
+use allocator_api::CheapAllocator;
+
+mod wrapper;
+pub use wrapper::{AssertCheap, BigArray, ByAddress, ByCopy};
+
+#[cfg(all(feature = "alloc", not(cheap_clone_no_atomic_ptr)))]
+mod shared_box;
+#[cfg(all(feature = "alloc", not(cheap_clone_no_atomic_ptr)))]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use shared_box::SharedBox;
+
+#[cfg(feature = "alloc")]
+mod dyn_cheap_clone;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use dyn_cheap_clone::{clone_box, DynCheapClone};
+
+/// Implements [`CheapClone`] for a list of types.
+///
+/// - `copy: A, B, ...` implements `cheap_clone` by copying (`*self`), the types must be `Copy`.
+/// - `delegate: A, B, ...` uses the default implementation, which calls `Clone::clone`
+///   and reports [`CloneCost::Composite`].
+///
+/// Both forms accept generic parameters, e.g. `copy<T: Copy>: Meters<T>` or
+/// `delegate<K: CheapClone, V: CheapClone>: Entry<K, V>`, for a single type.
+///
+/// ```rust
+/// use cheap_clone::{impl_cheap_clone, CheapClone};
+///
+/// #[derive(Clone, Copy)]
+/// struct Meters(f64);
+///
+/// #[derive(Clone, Copy)]
+/// struct Seconds(f64);
+///
+/// #[derive(Clone)]
+/// struct Handle<T>(T);
+///
+/// impl_cheap_clone!(copy: Meters, Seconds);
+/// impl_cheap_clone!(delegate<T: CheapClone>: Handle<T>);
+///
+/// assert_eq!(Handle(Meters(1.0)).cheap_clone().0 .0, 1.0);
+/// ```
+#[macro_export]
+macro_rules! impl_cheap_clone {
+  (copy: $($ty:ty),+ $(,)?) => {
     $(
-      impl crate::CheapClone for $ty {
+      impl $crate::CheapClone for $ty {
+        const COST: $crate::CloneCost = $crate::CloneCost::Copy;
+
+        #[inline]
         fn cheap_clone(&self) -> Self {
           *self
         }
       }
     )*
   };
+  (delegate: $($ty:ty),+ $(,)?) => {
+    $(
+      impl $crate::CheapClone for $ty {}
+    )*
+  };
+  (copy<$($param:ident $(: $($bound:ident)::+ $(+ $($bounds:ident)::+)*)?),+ $(,)?>: $ty:ty) => {
+    impl<$($param $(: $($bound)::+ $(+ $($bounds)::+)*)?),+> $crate::CheapClone for $ty {
+      const COST: $crate::CloneCost = $crate::CloneCost::Copy;
+
+      #[inline]
+      fn cheap_clone(&self) -> Self {
+        *self
+      }
+    }
+  };
+  (delegate<$($param:ident $(: $($bound:ident)::+ $(+ $($bounds:ident)::+)*)?),+ $(,)?>: $ty:ty) => {
+    impl<$($param $(: $($bound)::+ $(+ $($bounds)::+)*)?),+> $crate::CheapClone for $ty {}
+  };
 }
 
 /// Things that are fast to clone in the context of an application such as Graph Node
@@ -39,35 +179,316 @@ macro_rules! impl_cheap_clone_for_copy {
 /// - ✗ [`Vec<T>`](alloc::vec::Vec)
 /// - ✔ [`SmolStr`](smol_str::SmolStr)
 /// - ✗ [`String`]
+/// - ✗ [`Box<T>`](alloc::boxed::Box), use [`SharedBox<T>`](SharedBox) instead
+///
+/// The deprecated `legacy-box` feature restores the old `Box<T>` impl. The `strict` feature
+/// removes every impl which is not constant-time, even when it is enabled.
+///
+/// With the `derive` feature, `#[derive(CheapClone)]` implements the trait by calling
+/// [`cheap_clone`](CheapClone::cheap_clone) on every field, so adding a field which is
+/// not `CheapClone` is a compile error instead of a silently expensive clone.
+/// `Clone` still needs to be implemented (or derived) separately.
+///
+/// [`COST`](CheapClone::COST) classifies how expensive the clone is, so generic code can
+/// branch on it or assert it statically:
+///
+/// ```rust
+/// use cheap_clone::{CheapClone, CloneCost};
+///
+/// const _: () = assert!(<(u8, Option<char>) as CheapClone>::COST.is_at_most(CloneCost::Copy));
+/// ```
+#[cfg_attr(
+  cheap_clone_diagnostic_namespace,
+  diagnostic::on_unimplemented(
+    message = "`{Self}` is not `CheapClone`",
+    note = "wrap foreign `Copy` types in `ByCopy`, or foreign types with a constant-time `Clone` in `AssertCheap`"
+  )
+)]
 pub trait CheapClone: Clone {
+  /// How expensive [`cheap_clone`](CheapClone::cheap_clone) is.
+  ///
+  /// Defaults to [`CloneCost::Composite`], the most conservative classification.
+  const COST: CloneCost = CloneCost::Composite;
+
   /// Returns a copy of the value.
   fn cheap_clone(&self) -> Self {
     self.clone()
   }
 }
 
+/// Derives [`CheapClone`](trait@CheapClone) for structs and enums by calling
+/// [`cheap_clone`](CheapClone::cheap_clone) on every field.
+///
+/// `T: CheapClone` bounds are inferred for the fields whose type mentions a type parameter,
+/// and [`COST`](CheapClone::COST) is the most expensive cost of the fields (`Copy` if there
+/// are none).
+///
+/// ```rust
+/// use cheap_clone::{CheapClone, CloneCost};
+///
+/// #[derive(Clone, Copy, CheapClone)]
+/// struct Point {
+///   x: i32,
+///   y: i32,
+/// }
+///
+/// #[derive(Clone, CheapClone)]
+/// struct Labeled<T>(&'static str, T);
+///
+/// #[derive(Clone, CheapClone)]
+/// enum Shape<T> {
+///   Empty,
+///   Point(Point),
+///   Labeled { inner: Labeled<T> },
+/// }
+///
+/// let shape = Shape::Labeled { inner: Labeled("origin", Point { x: 0, y: 0 }) };
+/// match shape.cheap_clone() {
+///   Shape::Labeled { inner } => assert_eq!((inner.0, inner.1.x), ("origin", 0)),
+///   _ => unreachable!(),
+/// }
+/// assert_eq!(<Shape<Point> as CheapClone>::COST, CloneCost::Copy);
+/// ```
+///
+/// A field which is not `CheapClone` is a compile error pointing at the field:
+///
+/// ```rust,compile_fail,E0277
+/// use cheap_clone::CheapClone;
+///
+/// #[derive(Clone, CheapClone)]
+/// struct Names {
+///   names: Vec<String>,
+/// }
+/// ```
+///
+/// So is a generic field whose parameter is not `CheapClone` where the value is cloned:
+///
+/// ```rust,compile_fail,E0599
+/// use cheap_clone::CheapClone;
+///
+/// #[derive(Clone, CheapClone)]
+/// struct Wrapper<T>(T);
+///
+/// let name = Wrapper(String::from("expensive"));
+/// let copy = name.cheap_clone();
+/// ```
+#[cfg(feature = "derive")]
+#[cfg_attr(docsrs, doc(cfg(feature = "derive")))]
+pub use cheap_clone_derive::CheapClone;
+
+/// Rejects expensive `.clone()` calls in a function or in the methods of an impl block.
+///
+/// Every `receiver.clone()` call requires the receiver to be [`CheapClone`](trait@CheapClone).
+/// Annotate an expression or a `let` statement with `#[allow_expensive]` to keep its `.clone()`
+/// calls, e.g. on a tuple, a block or a single call.
+///
+/// ```rust
+/// use cheap_clone::audit;
+///
+/// #[audit]
+/// fn split(name: &&'static str, tags: &Vec<String>, ids: &Vec<u32>) -> (&'static str, Vec<String>, Vec<u32>) {
+///   let name = name.clone();
+///   let (tags, ids) = #[allow_expensive] (tags.clone(), ids.clone());
+///   (name, tags, ids)
+/// }
+///
+/// assert_eq!(split(&"a", &vec![String::from("b")], &vec![1]), ("a", vec![String::from("b")], vec![1]));
+/// ```
+///
+/// Any other `.clone()` is a compile error pointing at the call:
+///
+/// ```rust,compile_fail,E0277
+/// #[cheap_clone::audit]
+/// fn copy(tags: &Vec<String>) -> Vec<String> {
+///   tags.clone()
+/// }
+/// ```
+///
+/// So is `#[allow_expensive]` in any other position, e.g. on a match arm:
+///
+/// ```rust,compile_fail
+/// #[cheap_clone::audit]
+/// fn copy(tags: Option<&Vec<String>>) -> Vec<String> {
+///   match tags {
+///     #[allow_expensive]
+///     Some(tags) => tags.clone(),
+///     None => Vec::new(),
+///   }
+/// }
+/// ```
+#[cfg(feature = "derive")]
+#[cfg_attr(docsrs, doc(cfg(feature = "derive")))]
+pub use cheap_clone_derive::audit;
+
+/// Turns a struct with named fields into a cheaply cloneable handle.
+///
+/// `#[shared] struct Foo { a: A, b: B }` moves the fields into a private `FooInner` and makes
+/// `Foo` a wrapper around `Arc<FooInner>`, implementing `Clone` and `CheapClone` for it together
+/// with a `Foo::new(a, b)` constructor and `a()`/`b()` accessors that keep the visibility of
+/// their fields. `#[shared(local)]` uses `Rc` instead of `Arc`.
+///
+/// ```rust
+/// use cheap_clone::{shared, CheapClone};
+///
+/// /// The settings of a worker.
+/// #[shared]
+/// pub struct Config<T: Clone>
+/// where
+///   T: Default,
+/// {
+///   pub name: String,
+///   pub extra: T,
+/// }
+///
+/// #[shared(local)]
+/// struct Session {
+///   id: u64,
+/// }
+///
+/// let config = Config::new(String::from("worker"), 7u8);
+/// let copy = config.cheap_clone();
+/// assert_eq!((copy.name().as_str(), *copy.extra()), ("worker", 7));
+///
+/// assert_eq!(*Session::new(1).cheap_clone().id(), 1);
+/// ```
+///
+/// Doc comments stay on `Foo`, and `cfg` and `allow` attributes are applied to every generated
+/// item. All other attributes describe the data and are applied to `FooInner` only, so e.g.
+/// `#[derive(Debug)]` does not make `Foo` itself `Debug`:
+///
+/// ```rust,compile_fail,E0277
+/// #[cheap_clone::shared]
+/// #[derive(Debug)]
+/// struct Config {
+///   name: String,
+/// }
+///
+/// println!("{:?}", Config::new(String::from("worker")));
+/// ```
+#[cfg(all(feature = "derive", feature = "alloc"))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "derive", feature = "alloc"))))]
+pub use cheap_clone_derive::shared;
+
+#[doc(hidden)]
+pub mod __private {
+  pub use crate::cheap::{Cheap, CheapViaCheapClone, CheapViaCopy};
+
+  #[cfg(feature = "derive")]
+  pub use crate::audit::AuditClone;
+
+  #[cfg(feature = "alloc")]
+  pub use alloc::{boxed::Box, rc::Rc};
+
+  #[cfg(all(feature = "alloc", not(cheap_clone_no_atomic_ptr)))]
+  pub use alloc::sync::Arc;
+  #[cfg(all(cheap_clone_no_atomic_ptr, feature = "portable-atomic"))]
+  pub use portable_atomic_util::Arc;
+}
+
 #[cfg(feature = "bytes")]
-impl CheapClone for bytes::Bytes {}
+impl CheapClone for bytes::Bytes {
+  const COST: CloneCost = CloneCost::Composite;
+}
 
 #[cfg(feature = "smol_str")]
-impl CheapClone for smol_str::SmolStr {}
+impl CheapClone for smol_str::SmolStr {
+  const COST: CloneCost = CloneCost::AtomicRefCount;
+}
 
 #[cfg(feature = "alloc")]
 mod a {
-  use super::CheapClone;
+  #[cfg_attr(feature = "nightly-allocator-api", allow(unused_imports))]
+  use super::{CheapClone, CloneCost};
 
-  impl<T: ?Sized> CheapClone for alloc::rc::Rc<T> {}
-  impl<T: ?Sized> CheapClone for alloc::sync::Arc<T> {}
-  impl<T: ?Sized + CheapClone> CheapClone for alloc::boxed::Box<T> {}
+  #[cfg(not(feature = "nightly-allocator-api"))]
+  impl<T: ?Sized> CheapClone for alloc::rc::Rc<T> {
+    const COST: CloneCost = CloneCost::LocalRefCount;
+  }
+  // `Arc` does not exist on targets without atomic pointers (detected by the build script).
+  #[cfg(all(not(cheap_clone_no_atomic_ptr), not(feature = "nightly-allocator-api")))]
+  impl<T: ?Sized> CheapClone for alloc::sync::Arc<T> {
+    const COST: CloneCost = CloneCost::AtomicRefCount;
+  }
+  #[cfg(not(feature = "nightly-allocator-api"))]
+  impl<T: ?Sized> CheapClone for alloc::rc::Weak<T> {
+    const COST: CloneCost = CloneCost::LocalRefCount;
+  }
+  #[cfg(all(not(cheap_clone_no_atomic_ptr), not(feature = "nightly-allocator-api")))]
+  impl<T: ?Sized> CheapClone for alloc::sync::Weak<T> {
+    const COST: CloneCost = CloneCost::AtomicRefCount;
+  }
+  #[cfg(feature = "portable-atomic")]
+  impl<T: ?Sized> CheapClone for portable_atomic_util::Arc<T> {
+    const COST: CloneCost = CloneCost::AtomicRefCount;
+  }
+  #[cfg(feature = "portable-atomic")]
+  impl<T: ?Sized> CheapClone for portable_atomic_util::Weak<T> {
+    const COST: CloneCost = CloneCost::AtomicRefCount;
+  }
+  // `Box::clone` allocates, which breaks the constant-time rule of `CheapClone`.
+  // Kept behind the deprecated `legacy-box` feature, use `SharedBox` instead.
+  #[cfg(all(feature = "legacy-box", not(feature = "strict")))]
+  impl<T: CheapClone> CheapClone for alloc::boxed::Box<T> {
+    const COST: CloneCost = CloneCost::Composite;
+  }
 }
 
-#[cfg(feature = "std")]
-mod s {
-  use super::CheapClone;
+// With `allocator_api`, the impls above are generalized over the allocator. Cloning the pointer
+// also clones the allocator, so the allocator must be cheap to clone as well.
+#[cfg(feature = "nightly-allocator-api")]
+mod allocator_api {
+  use super::{CheapClone, CloneCost};
+  use alloc::alloc::{Allocator, Global};
+
+  /// The allocators of `CheapClone` pointers, as a single bound for the impls of the other traits.
+  pub(crate) trait CheapAllocator: Allocator + CheapClone {}
+
+  impl<A: Allocator + CheapClone> CheapAllocator for A {}
 
-  impl<T: ?Sized + CheapClone> CheapClone for std::pin::Pin<T> {}
+  impl CheapClone for Global {
+    const COST: CloneCost = CloneCost::Copy;
 
-  impl_cheap_clone_for_copy!(
+    fn cheap_clone(&self) -> Self {
+      *self
+    }
+  }
+
+  impl<T: ?Sized, A: Allocator + CheapClone> CheapClone for alloc::rc::Rc<T, A> {
+    const COST: CloneCost = CloneCost::LocalRefCount.max(A::COST);
+  }
+  impl<T: ?Sized, A: Allocator + CheapClone> CheapClone for alloc::rc::Weak<T, A> {
+    const COST: CloneCost = CloneCost::LocalRefCount.max(A::COST);
+  }
+
+  #[cfg(not(cheap_clone_no_atomic_ptr))]
+  impl<T: ?Sized, A: Allocator + CheapClone> CheapClone for alloc::sync::Arc<T, A> {
+    const COST: CloneCost = CloneCost::AtomicRefCount.max(A::COST);
+  }
+  #[cfg(not(cheap_clone_no_atomic_ptr))]
+  impl<T: ?Sized, A: Allocator + CheapClone> CheapClone for alloc::sync::Weak<T, A> {
+    const COST: CloneCost = CloneCost::AtomicRefCount.max(A::COST);
+  }
+}
+
+// `std::net` re-exports the `core::net` types since Rust 1.77, so they are available
+// without `std` on compilers which have them (detected by the build script).
+#[cfg(cheap_clone_core_net)]
+mod net {
+  impl_cheap_clone!(
+    copy:
+    core::net::IpAddr,
+    core::net::Ipv4Addr,
+    core::net::Ipv6Addr,
+    core::net::SocketAddr,
+    core::net::SocketAddrV4,
+    core::net::SocketAddrV6,
+  );
+}
+
+#[cfg(all(not(cheap_clone_core_net), feature = "std"))]
+mod net {
+  impl_cheap_clone!(
+    copy:
     std::net::IpAddr,
     std::net::Ipv4Addr,
     std::net::Ipv6Addr,
@@ -77,12 +498,44 @@ mod s {
   );
 }
 
-impl<T: CheapClone> CheapClone for Option<T> {}
-impl<T: CheapClone, E: CheapClone> CheapClone for Result<T, E> {}
+impl<P: CheapClone> CheapClone for core::pin::Pin<P> {
+  const COST: CloneCost = P::COST;
+}
+
+// `Option`, `Result` and `Either` call `cheap_clone` on their values instead of relying on the
+// default `clone()`, so the checks of their `cheap_clone` (e.g. the size budget of arrays) still
+// apply. `Pin` keeps its `clone()`: it only wraps pointers, never arrays.
+impl<T: CheapClone> CheapClone for Option<T> {
+  const COST: CloneCost = T::COST;
+
+  fn cheap_clone(&self) -> Self {
+    self.as_ref().map(T::cheap_clone)
+  }
+}
+impl<T: CheapClone, E: CheapClone> CheapClone for Result<T, E> {
+  const COST: CloneCost = T::COST.max(E::COST);
+
+  fn cheap_clone(&self) -> Self {
+    match self {
+      Ok(value) => Ok(value.cheap_clone()),
+      Err(err) => Err(err.cheap_clone()),
+    }
+  }
+}
 #[cfg(feature = "either")]
-impl<L: CheapClone, R: CheapClone> CheapClone for either::Either<L, R> {}
+impl<L: CheapClone, R: CheapClone> CheapClone for either::Either<L, R> {
+  const COST: CloneCost = L::COST.max(R::COST);
+
+  fn cheap_clone(&self) -> Self {
+    match self {
+      either::Either::Left(left) => either::Either::Left(left.cheap_clone()),
+      either::Either::Right(right) => either::Either::Right(right.cheap_clone()),
+    }
+  }
+}
 
-impl_cheap_clone_for_copy! {
+impl_cheap_clone! {
+  copy:
   (),
   bool, char, f32, f64, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize,
   core::num::NonZeroI8,
@@ -97,25 +550,126 @@ impl_cheap_clone_for_copy! {
   core::num::NonZeroU64,
   core::num::NonZeroU128,
   core::num::NonZeroUsize,
-  &str
 }
 
-impl<T: Copy, const N: usize> CheapClone for [T; N] {
+/// The maximum size in bytes of an array `[T; N]` for which
+/// [`cheap_clone`](CheapClone::cheap_clone) compiles.
+///
+/// Copying a large array is a `memcpy` of the whole array, so e.g. `[u8; 1_000_000]` is not
+/// cheap although `u8` is. The budget is 256 bytes, or 4096 bytes with the
+/// `large-array-budget` feature. Wrap arrays which should be cloned regardless of their size
+/// in [`BigArray`].
+///
+/// The limit applies wherever an array is cheaply cloned, also inside another value:
+///
+/// ```rust,compile_fail
+/// use cheap_clone::CheapClone;
+///
+/// let copy = [0u8; 100_000].cheap_clone();
+/// ```
+///
+/// ```rust,compile_fail
+/// use cheap_clone::CheapClone;
+///
+/// let copy = Some([0u8; 100_000]).cheap_clone();
+/// ```
+///
+/// The error is raised when the clone is compiled for a concrete `N` (like an overflowing
+/// constant), so `cargo build` reports it but `cargo check` does not. Evaluating the
+/// [`COST`](CheapClone::COST) of an oversized array fails the same way.
+/// [`AssertCheap`] does not require the wrapped value to be `CheapClone` and therefore does not
+/// check the limit.
+///
+/// [`cheap!`] copies `Copy` values without calling `cheap_clone`, so it checks every `Copy`
+/// value against the same limit, arrays or not:
+///
+/// ```rust,compile_fail
+/// let copy = cheap_clone::cheap!([0u8; 100_000]);
+/// ```
+pub const MAX_ARRAY_BYTES: usize = if cfg!(feature = "large-array-budget") {
+  4096
+} else {
+  256
+};
+
+pub(crate) struct ArrayBudget<T, const N: usize>(core::marker::PhantomData<T>);
+
+impl<T, const N: usize> ArrayBudget<T, N> {
+  /// Fails to evaluate (and therefore to compile) if `[T; N]` exceeds [`MAX_ARRAY_BYTES`].
+  pub(crate) const ARRAY_EXCEEDS_MAX_ARRAY_BYTES: () =
+    [()][(core::mem::size_of::<[T; N]>() > MAX_ARRAY_BYTES) as usize];
+}
+
+/// Clones every element of `array` with [`CheapClone::cheap_clone`].
+#[inline]
+fn cheap_clone_array<T: CheapClone, const N: usize>(array: &[T; N]) -> [T; N] {
+  let mut iter = array.iter();
+  [(); N].map(|_| match iter.next() {
+    Some(item) => item.cheap_clone(),
+    None => unreachable!(),
+  })
+}
+
+impl<T: CheapClone, const N: usize> CheapClone for [T; N] {
+  #[allow(clippy::let_unit_value)]
+  const COST: CloneCost = {
+    let () = ArrayBudget::<T, N>::ARRAY_EXCEEDS_MAX_ARRAY_BYTES;
+    T::COST
+  };
+
+  #[allow(clippy::let_unit_value)]
   fn cheap_clone(&self) -> Self {
-    *self
+    let () = ArrayBudget::<T, N>::ARRAY_EXCEEDS_MAX_ARRAY_BYTES;
+    cheap_clone_array(self)
   }
 }
 
-impl<'a, T> CheapClone for &'a T {
+impl<T: ?Sized> CheapClone for &T {
+  const COST: CloneCost = CloneCost::Copy;
+
   fn cheap_clone(&self) -> Self {
     self
   }
 }
 
+impl<T: ?Sized> CheapClone for *const T {
+  const COST: CloneCost = CloneCost::Copy;
+
+  fn cheap_clone(&self) -> Self {
+    *self
+  }
+}
+
+impl<T: ?Sized> CheapClone for *mut T {
+  const COST: CloneCost = CloneCost::Copy;
+
+  fn cheap_clone(&self) -> Self {
+    *self
+  }
+}
+
+impl<T: ?Sized> CheapClone for core::marker::PhantomData<T> {
+  const COST: CloneCost = CloneCost::Copy;
+
+  fn cheap_clone(&self) -> Self {
+    *self
+  }
+}
+
+impl<T: ?Sized> CheapClone for core::ptr::NonNull<T> {
+  const COST: CloneCost = CloneCost::Copy;
+
+  fn cheap_clone(&self) -> Self {
+    *self
+  }
+}
+
 macro_rules! impl_cheap_clone_for_tuple {
   ($($param:literal),+ $(,)?) => {
     paste::paste! {
       impl<$([< T $param >]: CheapClone),+> CheapClone for ($([< T $param >],)+) {
+        const COST: CloneCost = CloneCost::Copy$(.max([< T $param >]::COST))+;
+
         fn cheap_clone(&self) -> Self {
           ($(self.$param.cheap_clone(),)+)
         }
@@ -124,35 +678,4 @@ macro_rules! impl_cheap_clone_for_tuple {
   };
 }
 
-impl_cheap_clone_for_tuple!(0);
-impl_cheap_clone_for_tuple!(0, 1);
-impl_cheap_clone_for_tuple!(0, 1, 2);
-impl_cheap_clone_for_tuple!(0, 1, 2, 3);
-impl_cheap_clone_for_tuple!(0, 1, 2, 3, 4);
-impl_cheap_clone_for_tuple!(0, 1, 2, 3, 4, 5);
-impl_cheap_clone_for_tuple!(0, 1, 2, 3, 4, 5, 6);
-impl_cheap_clone_for_tuple!(0, 1, 2, 3, 4, 5, 6, 7);
-impl_cheap_clone_for_tuple!(0, 1, 2, 3, 4, 5, 6, 7, 8);
-impl_cheap_clone_for_tuple!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9);
-impl_cheap_clone_for_tuple!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10);
-impl_cheap_clone_for_tuple!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11);
-impl_cheap_clone_for_tuple!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12);
-impl_cheap_clone_for_tuple!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13);
-impl_cheap_clone_for_tuple!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14);
-impl_cheap_clone_for_tuple!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15);
-impl_cheap_clone_for_tuple!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16);
-impl_cheap_clone_for_tuple!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17);
-impl_cheap_clone_for_tuple!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18);
-impl_cheap_clone_for_tuple!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19);
-impl_cheap_clone_for_tuple!(
-  0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20
-);
-impl_cheap_clone_for_tuple!(
-  0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21
-);
-impl_cheap_clone_for_tuple!(
-  0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22
-);
-impl_cheap_clone_for_tuple!(
-  0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23
-);
+for_each_tuple!(impl_cheap_clone_for_tuple);
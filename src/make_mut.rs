@@ -0,0 +1,131 @@
+use super::CheapClone;
+
+/// Cheaply cloned values which can be turned back into an owned, mutable value.
+///
+/// The value is moved out if `this` is its only handle, and cloned otherwise. Some types can
+/// only hand out a different owned type (e.g. [`Bytes`](bytes::Bytes) gives a
+/// [`BytesMut`](bytes::BytesMut)), so mutating in place is the separate [`MakeMut`] trait.
+pub trait Unshare: CheapClone {
+  /// The owned value.
+  type Owned;
+
+  /// Returns the owned value if `this` is its only handle, or `this` otherwise.
+  fn try_unwrap(this: Self) -> Result<Self::Owned, Self>;
+
+  /// Returns the owned value, cloning it if `this` is not its only handle.
+  fn unwrap_or_clone(this: Self) -> Self::Owned;
+}
+
+/// Copy-on-write access to cheaply cloned pointers.
+///
+/// ```rust
+/// use cheap_clone::{CheapClone, MakeMut};
+/// # #[cfg(feature = "alloc")]
+/// # {
+/// use std::sync::Arc;
+///
+/// let mut a = Arc::new(vec![1]);
+/// let b = a.cheap_clone();
+///
+/// assert!(MakeMut::get_mut(&mut a).is_none());
+/// MakeMut::make_mut(&mut a).push(2);
+/// assert_eq!(*a, [1, 2]);
+/// assert_eq!(*b, [1]);
+/// # }
+/// ```
+pub trait MakeMut: Unshare {
+  /// Returns a mutable reference to the value, cloning it first if `this` is not its only
+  /// handle.
+  fn make_mut(this: &mut Self) -> &mut Self::Owned;
+
+  /// Returns a mutable reference to the value if `this` is its only handle.
+  fn get_mut(this: &mut Self) -> Option<&mut Self::Owned>;
+}
+
+#[cfg(feature = "alloc")]
+macro_rules! impl_make_mut_for_pointer {
+  ($($ptr:ident)::+ $(<$($generic:ident: $bound:path),*>)?) => {
+    impl<T: Clone $($(, $generic: $bound)*)?> Unshare for $($ptr)::+<T $($(, $generic)*)?> {
+      type Owned = T;
+
+      #[inline]
+      fn try_unwrap(this: Self) -> Result<T, Self> {
+        $($ptr)::+::try_unwrap(this)
+      }
+
+      #[inline]
+      fn unwrap_or_clone(this: Self) -> T {
+        $($ptr)::+::try_unwrap(this).unwrap_or_else(|this| T::clone(&this))
+      }
+    }
+
+    impl<T: Clone $($(, $generic: $bound)*)?> MakeMut for $($ptr)::+<T $($(, $generic)*)?> {
+      #[inline]
+      fn make_mut(this: &mut Self) -> &mut T {
+        $($ptr)::+::make_mut(this)
+      }
+
+      #[inline]
+      fn get_mut(this: &mut Self) -> Option<&mut T> {
+        $($ptr)::+::get_mut(this)
+      }
+    }
+  };
+}
+
+#[cfg(all(feature = "alloc", not(feature = "nightly-allocator-api")))]
+mod a {
+  use super::{MakeMut, Unshare};
+
+  impl_make_mut_for_pointer!(alloc::rc::Rc);
+
+  #[cfg(not(cheap_clone_no_atomic_ptr))]
+  impl_make_mut_for_pointer!(alloc::sync::Arc);
+}
+
+#[cfg(feature = "nightly-allocator-api")]
+mod allocator_api {
+  use super::{MakeMut, Unshare};
+  use crate::CheapAllocator;
+
+  impl_make_mut_for_pointer!(alloc::rc::Rc<A: CheapAllocator>);
+
+  #[cfg(not(cheap_clone_no_atomic_ptr))]
+  impl_make_mut_for_pointer!(alloc::sync::Arc<A: CheapAllocator>);
+}
+
+#[cfg(feature = "portable-atomic")]
+impl_make_mut_for_pointer!(portable_atomic_util::Arc);
+
+/// Reuses the buffer if it is unique, and copies the bytes otherwise.
+#[cfg(feature = "bytes")]
+impl Unshare for bytes::Bytes {
+  type Owned = bytes::BytesMut;
+
+  #[inline]
+  fn try_unwrap(this: Self) -> Result<bytes::BytesMut, Self> {
+    this.try_into_mut()
+  }
+
+  #[inline]
+  fn unwrap_or_clone(this: Self) -> bytes::BytesMut {
+    bytes::BytesMut::from(this)
+  }
+}
+
+/// `SmolStr` does not expose whether it is shared, so `try_unwrap` always fails and
+/// `unwrap_or_clone` always copies into a new `String`.
+#[cfg(all(feature = "smol_str", feature = "alloc"))]
+impl Unshare for smol_str::SmolStr {
+  type Owned = alloc::string::String;
+
+  #[inline]
+  fn try_unwrap(this: Self) -> Result<alloc::string::String, Self> {
+    Err(this)
+  }
+
+  #[inline]
+  fn unwrap_or_clone(this: Self) -> alloc::string::String {
+    this.into()
+  }
+}
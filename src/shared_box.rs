@@ -0,0 +1,167 @@
+use alloc::{
+  boxed::Box,
+  sync::{Arc, Weak},
+};
+use core::{borrow::Borrow, fmt, ops::Deref};
+
+use super::{CheapClone, CloneCost, Downgrade, SharedHandle};
+
+/// An immutable, shared box which is cheap to clone.
+///
+/// `Box::clone` allocates and deep copies the value, so `Box<T>` is not [`CheapClone`] (unless
+/// the deprecated `legacy-box` feature is enabled). `SharedBox<T>` moves the value into an
+/// [`Arc`] once, and every clone afterwards only increments the reference count.
+pub struct SharedBox<T: ?Sized>(Arc<T>);
+
+impl<T> SharedBox<T> {
+  /// Moves `value` into a new `SharedBox`.
+  #[inline]
+  pub fn new(value: T) -> Self {
+    Self(Arc::new(value))
+  }
+}
+
+impl<T: ?Sized> SharedBox<T> {
+  /// Returns the underlying [`Arc`].
+  #[inline]
+  pub fn into_arc(self) -> Arc<T> {
+    self.0
+  }
+}
+
+impl<T: ?Sized> Clone for SharedBox<T> {
+  #[inline]
+  fn clone(&self) -> Self {
+    Self(self.0.clone())
+  }
+}
+
+impl<T: ?Sized> CheapClone for SharedBox<T> {
+  const COST: CloneCost = CloneCost::AtomicRefCount;
+}
+
+impl<T: ?Sized> Downgrade for SharedBox<T> {
+  type Weak = Weak<T>;
+
+  #[inline]
+  fn downgrade(this: &Self) -> Weak<T> {
+    Arc::downgrade(&this.0)
+  }
+
+  #[inline]
+  fn upgrade(weak: &Weak<T>) -> Option<Self> {
+    weak.upgrade().map(Self)
+  }
+}
+
+impl<T: ?Sized> SharedHandle for SharedBox<T> {
+  #[inline]
+  fn ptr_eq(this: &Self, other: &Self) -> bool {
+    Arc::ptr_eq(&this.0, &other.0)
+  }
+
+  #[inline]
+  fn strong_count(this: &Self) -> Option<usize> {
+    Some(Arc::strong_count(&this.0))
+  }
+
+  #[inline]
+  fn weak_count(this: &Self) -> Option<usize> {
+    Some(Arc::weak_count(&this.0))
+  }
+}
+
+impl<T: ?Sized> Deref for SharedBox<T> {
+  type Target = T;
+
+  #[inline]
+  fn deref(&self) -> &T {
+    &self.0
+  }
+}
+
+impl<T: ?Sized> AsRef<T> for SharedBox<T> {
+  #[inline]
+  fn as_ref(&self) -> &T {
+    &self.0
+  }
+}
+
+impl<T: ?Sized> Borrow<T> for SharedBox<T> {
+  #[inline]
+  fn borrow(&self) -> &T {
+    &self.0
+  }
+}
+
+impl<T> From<T> for SharedBox<T> {
+  #[inline]
+  fn from(value: T) -> Self {
+    Self::new(value)
+  }
+}
+
+impl<T: ?Sized> From<Box<T>> for SharedBox<T> {
+  #[inline]
+  fn from(value: Box<T>) -> Self {
+    Self(Arc::from(value))
+  }
+}
+
+impl<T: ?Sized> From<Arc<T>> for SharedBox<T> {
+  #[inline]
+  fn from(value: Arc<T>) -> Self {
+    Self(value)
+  }
+}
+
+impl<T: ?Sized + fmt::Debug> fmt::Debug for SharedBox<T> {
+  #[inline]
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    self.0.fmt(f)
+  }
+}
+
+impl<T: ?Sized + fmt::Display> fmt::Display for SharedBox<T> {
+  #[inline]
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    self.0.fmt(f)
+  }
+}
+
+impl<T: ?Sized + PartialEq> PartialEq for SharedBox<T> {
+  #[inline]
+  fn eq(&self, other: &Self) -> bool {
+    self.0 == other.0
+  }
+}
+
+impl<T: ?Sized + Eq> Eq for SharedBox<T> {}
+
+impl<T: ?Sized + PartialOrd> PartialOrd for SharedBox<T> {
+  #[inline]
+  fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+    self.0.partial_cmp(&other.0)
+  }
+}
+
+impl<T: ?Sized + Ord> Ord for SharedBox<T> {
+  #[inline]
+  fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+    self.0.cmp(&other.0)
+  }
+}
+
+impl<T: ?Sized + core::hash::Hash> core::hash::Hash for SharedBox<T> {
+  #[inline]
+  fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+    self.0.hash(state)
+  }
+}
+
+impl<T: Default> Default for SharedBox<T> {
+  #[inline]
+  fn default() -> Self {
+    Self::new(T::default())
+  }
+}
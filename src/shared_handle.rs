@@ -0,0 +1,179 @@
+use super::CheapClone;
+
+/// Introspection of cheaply cloned handles which share their storage, e.g.
+/// `SharedHandle::strong_count(&handle)`.
+pub trait SharedHandle: CheapClone {
+  /// Returns `true` if both handles share the same storage.
+  fn ptr_eq(this: &Self, other: &Self) -> bool;
+
+  /// Returns the number of strong handles to the storage, or `None` if the type does not
+  /// track it (e.g. [`Bytes`](bytes::Bytes)).
+  fn strong_count(this: &Self) -> Option<usize>;
+
+  /// Returns the number of weak handles to the storage, or `None` if the type does not
+  /// track it.
+  fn weak_count(this: &Self) -> Option<usize>;
+}
+
+#[cfg(feature = "alloc")]
+macro_rules! impl_shared_handle_for_pointer {
+  ($($ptr:ident)::+ $(<$($generic:ident: $bound:path),*>)?) => {
+    impl<T: ?Sized $($(, $generic: $bound)*)?> SharedHandle for $($ptr)::+<T $($(, $generic)*)?> {
+      #[inline]
+      fn ptr_eq(this: &Self, other: &Self) -> bool {
+        $($ptr)::+::ptr_eq(this, other)
+      }
+
+      #[inline]
+      fn strong_count(this: &Self) -> Option<usize> {
+        Some($($ptr)::+::strong_count(this))
+      }
+
+      #[inline]
+      fn weak_count(this: &Self) -> Option<usize> {
+        Some($($ptr)::+::weak_count(this))
+      }
+    }
+  };
+}
+
+#[cfg(feature = "alloc")]
+macro_rules! impl_shared_handle_for_weak {
+  ($($ptr:ident)::+ $(<$($generic:ident: $bound:path),*>)?) => {
+    impl<T: ?Sized $($(, $generic: $bound)*)?> SharedHandle for $($ptr)::+<T $($(, $generic)*)?> {
+      #[inline]
+      fn ptr_eq(this: &Self, other: &Self) -> bool {
+        this.ptr_eq(other)
+      }
+
+      #[inline]
+      fn strong_count(this: &Self) -> Option<usize> {
+        Some(this.strong_count())
+      }
+
+      #[inline]
+      fn weak_count(this: &Self) -> Option<usize> {
+        Some(this.weak_count())
+      }
+    }
+  };
+}
+
+#[cfg(all(feature = "alloc", not(feature = "nightly-allocator-api")))]
+mod a {
+  use super::SharedHandle;
+
+  impl_shared_handle_for_pointer!(alloc::rc::Rc);
+  impl_shared_handle_for_weak!(alloc::rc::Weak);
+
+  #[cfg(not(cheap_clone_no_atomic_ptr))]
+  impl_shared_handle_for_pointer!(alloc::sync::Arc);
+  #[cfg(not(cheap_clone_no_atomic_ptr))]
+  impl_shared_handle_for_weak!(alloc::sync::Weak);
+}
+
+#[cfg(feature = "nightly-allocator-api")]
+mod allocator_api {
+  use super::SharedHandle;
+  use crate::CheapAllocator;
+
+  impl_shared_handle_for_pointer!(alloc::rc::Rc<A: CheapAllocator>);
+  impl_shared_handle_for_weak!(alloc::rc::Weak<A: CheapAllocator>);
+
+  #[cfg(not(cheap_clone_no_atomic_ptr))]
+  impl_shared_handle_for_pointer!(alloc::sync::Arc<A: CheapAllocator>);
+  #[cfg(not(cheap_clone_no_atomic_ptr))]
+  impl_shared_handle_for_weak!(alloc::sync::Weak<A: CheapAllocator>);
+}
+
+#[cfg(feature = "portable-atomic")]
+impl_shared_handle_for_pointer!(portable_atomic_util::Arc);
+#[cfg(feature = "portable-atomic")]
+impl_shared_handle_for_weak!(portable_atomic_util::Weak);
+
+/// Two `Bytes` are the same handle if they view the same slice of the same buffer.
+#[cfg(feature = "bytes")]
+impl SharedHandle for bytes::Bytes {
+  #[inline]
+  fn ptr_eq(this: &Self, other: &Self) -> bool {
+    this.as_ptr() == other.as_ptr() && this.len() == other.len()
+  }
+
+  #[inline]
+  fn strong_count(_: &Self) -> Option<usize> {
+    None
+  }
+
+  #[inline]
+  fn weak_count(_: &Self) -> Option<usize> {
+    None
+  }
+}
+
+#[cfg(feature = "alloc")]
+mod pin {
+  use super::SharedHandle;
+  use core::pin::Pin;
+
+  /// Returns the pointer inside of a `Pin`.
+  fn pointer<P>(pin: &Pin<P>) -> &P {
+    // SAFETY: `Pin<P>` is `#[repr(transparent)]` over `P`, so the cast is valid. The result is
+    // a shared reference to the pointer, which the pointers of this crate (`Rc`, `Arc`,
+    // `SharedBox`) cannot move or mutably borrow the pinned value through, and it is only used
+    // to compare addresses and read reference counts.
+    unsafe { &*(pin as *const Pin<P> as *const P) }
+  }
+
+  macro_rules! impl_shared_handle_for_pin {
+    ($($ptr:ident)::+) => {
+      impl<T: ?Sized> SharedHandle for Pin<$($ptr)::+<T>> {
+        #[inline]
+        fn ptr_eq(this: &Self, other: &Self) -> bool {
+          SharedHandle::ptr_eq(pointer(this), pointer(other))
+        }
+
+        #[inline]
+        fn strong_count(this: &Self) -> Option<usize> {
+          SharedHandle::strong_count(pointer(this))
+        }
+
+        #[inline]
+        fn weak_count(this: &Self) -> Option<usize> {
+          SharedHandle::weak_count(pointer(this))
+        }
+      }
+    };
+  }
+
+  impl_shared_handle_for_pin!(alloc::rc::Rc);
+  #[cfg(not(cheap_clone_no_atomic_ptr))]
+  impl_shared_handle_for_pin!(alloc::sync::Arc);
+  #[cfg(not(cheap_clone_no_atomic_ptr))]
+  impl_shared_handle_for_pin!(crate::SharedBox);
+}
+
+// Tuples are the same handle if all of their elements are, their counts are not tracked.
+macro_rules! impl_shared_handle_for_tuple {
+  ($($param:literal),+ $(,)?) => {
+    paste::paste! {
+      impl<$([< T $param >]: SharedHandle),+> SharedHandle for ($([< T $param >],)+) {
+        #[inline]
+        fn ptr_eq(this: &Self, other: &Self) -> bool {
+          $(SharedHandle::ptr_eq(&this.$param, &other.$param))&&+
+        }
+
+        #[inline]
+        fn strong_count(_: &Self) -> Option<usize> {
+          None
+        }
+
+        #[inline]
+        fn weak_count(_: &Self) -> Option<usize> {
+          None
+        }
+      }
+    }
+  };
+}
+
+for_each_tuple!(impl_shared_handle_for_tuple);
@@ -0,0 +1,196 @@
+//! Helpers to check that [`CheapClone`] implementations never allocate.
+//!
+//! Install [`CountingAllocator`] as the global allocator of the test binary, then use
+//! [`assert_cheap_clone_no_alloc`] or the [`no_alloc_test!`](crate::no_alloc_test) macro.
+//!
+//! ```rust
+//! use cheap_clone::testing::{assert_cheap_clone_no_alloc, CountingAllocator};
+//! use std::{rc::Rc, sync::Arc};
+//!
+//! #[global_allocator]
+//! static ALLOC: CountingAllocator = CountingAllocator::new();
+//!
+//! fn main() {
+//!   assert_cheap_clone_no_alloc(&Arc::new(1));
+//!   assert_cheap_clone_no_alloc(&(Rc::<str>::from("a"), Some(2u8)));
+//! }
+//! ```
+//!
+//! Allocations are counted per thread, so tests running in parallel do not affect each other.
+
+use core::cell::Cell;
+use std::{
+  alloc::{GlobalAlloc, Layout, System},
+  sync::atomic::{AtomicBool, Ordering},
+  thread_local,
+};
+
+use super::CheapClone;
+
+/// How many times [`assert_cheap_clone_no_alloc`] clones the value.
+const ITERATIONS: usize = 16;
+
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+  // `const` initializers never allocate on first access, which would call back into
+  // `CountingAllocator`. They need Rust 1.59, above the MSRV of the rest of the crate.
+  static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+  static DEALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+fn bump(counter: &'static std::thread::LocalKey<Cell<usize>>) {
+  INSTALLED.store(true, Ordering::Relaxed);
+  // The thread local may already be destroyed while the thread is shutting down.
+  let _ = counter.try_with(|c| c.set(c.get() + 1));
+}
+
+/// A [`GlobalAlloc`] which counts the allocations and deallocations of every thread, and
+/// forwards them to another allocator ([`System`] by default).
+#[derive(Debug, Default)]
+pub struct CountingAllocator<A = System> {
+  inner: A,
+}
+
+impl CountingAllocator {
+  /// Creates a counting allocator on top of the [`System`] allocator.
+  #[inline]
+  pub const fn new() -> Self {
+    Self { inner: System }
+  }
+}
+
+impl<A> CountingAllocator<A> {
+  /// Creates a counting allocator on top of `inner`.
+  #[inline]
+  pub const fn with_allocator(inner: A) -> Self {
+    Self { inner }
+  }
+}
+
+// SAFETY: every call is forwarded to the inner allocator unchanged.
+unsafe impl<A: GlobalAlloc> GlobalAlloc for CountingAllocator<A> {
+  unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+    bump(&ALLOCATIONS);
+    self.inner.alloc(layout)
+  }
+
+  unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+    bump(&ALLOCATIONS);
+    self.inner.alloc_zeroed(layout)
+  }
+
+  unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+    bump(&ALLOCATIONS);
+    self.inner.realloc(ptr, layout, new_size)
+  }
+
+  unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+    bump(&DEALLOCATIONS);
+    self.inner.dealloc(ptr, layout)
+  }
+}
+
+/// The allocations and deallocations observed on the current thread by [`count_allocations`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct AllocStats {
+  /// Number of calls to `alloc`, `alloc_zeroed` and `realloc`.
+  pub allocations: usize,
+  /// Number of calls to `dealloc`.
+  pub deallocations: usize,
+}
+
+impl AllocStats {
+  fn now() -> Self {
+    Self {
+      allocations: ALLOCATIONS.with(Cell::get),
+      deallocations: DEALLOCATIONS.with(Cell::get),
+    }
+  }
+}
+
+/// Runs `f` and returns its result together with the allocations it made on the current thread.
+///
+/// # Panics
+///
+/// Panics if [`CountingAllocator`] is not installed as the `#[global_allocator]`.
+pub fn count_allocations<R>(f: impl FnOnce() -> R) -> (R, AllocStats) {
+  assert!(
+    INSTALLED.load(Ordering::Relaxed),
+    "`CountingAllocator` must be installed with `#[global_allocator]` to count allocations"
+  );
+
+  let before = AllocStats::now();
+  let result = f();
+  let after = AllocStats::now();
+  (
+    result,
+    AllocStats {
+      allocations: after.allocations - before.allocations,
+      deallocations: after.deallocations - before.deallocations,
+    },
+  )
+}
+
+/// Asserts that cloning `value` with [`cheap_clone`](CheapClone::cheap_clone) and dropping the
+/// clone neither allocates nor deallocates, from the first clone on.
+///
+/// # Panics
+///
+/// Panics if any clone allocates or deallocates, or if [`CountingAllocator`] is not installed.
+pub fn assert_cheap_clone_no_alloc<T: CheapClone>(value: &T) {
+  assert_no_alloc(value, 0);
+}
+
+/// Like [`assert_cheap_clone_no_alloc`], but clones `value` once before counting starts.
+///
+/// Only use it for values which do one-time lazy work on their first clone, e.g. a `Bytes`
+/// created from a `Vec`, which moves the buffer into a shared allocation.
+///
+/// # Panics
+///
+/// Panics if any clone after the first allocates or deallocates, or if [`CountingAllocator`]
+/// is not installed.
+pub fn assert_cheap_clone_no_alloc_after_warmup<T: CheapClone>(value: &T) {
+  drop(value.cheap_clone());
+  assert_no_alloc(value, 1);
+}
+
+fn assert_no_alloc<T: CheapClone>(value: &T, warmup: usize) {
+  let ((), stats) = count_allocations(|| {
+    for _ in 0..ITERATIONS {
+      drop(value.cheap_clone());
+    }
+  });
+  assert!(
+    stats == AllocStats::default(),
+    "cloning `{}` {} times (after {} warm-up clones) caused {} allocation(s) and {} deallocation(s)",
+    core::any::type_name::<T>(),
+    ITERATIONS,
+    warmup,
+    stats.allocations,
+    stats.deallocations,
+  );
+}
+
+/// Defines a `#[test]` which fails if cheaply cloning the given value allocates or
+/// deallocates, see [`assert_cheap_clone_no_alloc`](crate::testing::assert_cheap_clone_no_alloc).
+///
+/// The test binary must install [`CountingAllocator`](crate::testing::CountingAllocator) as
+/// its `#[global_allocator]`.
+///
+/// `tests/no_alloc.rs` runs it over the impls of this crate.
+///
+/// ```rust,ignore
+/// cheap_clone::no_alloc_test!(arc_clone_does_not_allocate, std::sync::Arc::new(1u8));
+/// ```
+#[macro_export]
+macro_rules! no_alloc_test {
+  ($name:ident, $value:expr $(,)?) => {
+    #[test]
+    fn $name() {
+      let value = $value;
+      $crate::testing::assert_cheap_clone_no_alloc(&value);
+    }
+  };
+}
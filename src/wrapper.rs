@@ -0,0 +1,350 @@
+use core::{
+  borrow::Borrow,
+  cmp::Ordering,
+  fmt,
+  hash::{Hash, Hasher},
+  ops::{Deref, DerefMut},
+};
+
+use super::{CheapClone, CloneCost, SharedHandle};
+
+macro_rules! transparent_wrapper {
+  ($($name:ident),+ $(,)?) => {
+    $(
+      impl<T> $name<T> {
+        #[doc = concat!("Wraps `value` in a `", stringify!($name), "`.")]
+        #[inline]
+        pub const fn new(value: T) -> Self {
+          Self(value)
+        }
+
+        /// Returns the wrapped value.
+        #[inline]
+        pub fn into_inner(self) -> T {
+          self.0
+        }
+      }
+
+      impl<T> From<T> for $name<T> {
+        #[inline]
+        fn from(value: T) -> Self {
+          Self(value)
+        }
+      }
+
+      impl<T> Deref for $name<T> {
+        type Target = T;
+
+        #[inline]
+        fn deref(&self) -> &T {
+          &self.0
+        }
+      }
+
+      impl<T> DerefMut for $name<T> {
+        #[inline]
+        fn deref_mut(&mut self) -> &mut T {
+          &mut self.0
+        }
+      }
+
+      impl<T> AsRef<T> for $name<T> {
+        #[inline]
+        fn as_ref(&self) -> &T {
+          &self.0
+        }
+      }
+
+      impl<T> AsMut<T> for $name<T> {
+        #[inline]
+        fn as_mut(&mut self) -> &mut T {
+          &mut self.0
+        }
+      }
+
+      impl<T: fmt::Debug> fmt::Debug for $name<T> {
+        #[inline]
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+          self.0.fmt(f)
+        }
+      }
+
+      impl<T: fmt::Display> fmt::Display for $name<T> {
+        #[inline]
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+          self.0.fmt(f)
+        }
+      }
+    )*
+  };
+}
+
+/// Makes any `Copy` type [`CheapClone`] by copying it.
+///
+/// This is an escape hatch for foreign `Copy` types this crate does not (and because of the
+/// orphan rule, your crate cannot) implement `CheapClone` for.
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct ByCopy<T>(T);
+
+impl<T: Copy> CheapClone for ByCopy<T> {
+  const COST: CloneCost = CloneCost::Copy;
+
+  #[inline]
+  fn cheap_clone(&self) -> Self {
+    *self
+  }
+}
+
+/// Asserts that cloning the wrapped value is cheap, and implements [`CheapClone`] by calling
+/// `Clone::clone`.
+///
+/// Use it for foreign types whose `Clone` is known to be constant-time, e.g. handles which are
+/// `Arc` based internally. The assertion is not checked.
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct AssertCheap<T>(T);
+
+impl<T: Clone> CheapClone for AssertCheap<T> {
+  const COST: CloneCost = CloneCost::Composite;
+}
+
+transparent_wrapper!(ByCopy, AssertCheap);
+
+/// An array which is [`CheapClone`] regardless of its size.
+///
+/// `[T; N]` only compiles `cheap_clone` if it fits into [`MAX_ARRAY_BYTES`](crate::MAX_ARRAY_BYTES).
+/// Use `BigArray` where copying a large array is known and accepted.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct BigArray<T, const N: usize>([T; N]);
+
+impl<T, const N: usize> BigArray<T, N> {
+  /// Wraps `array` in a `BigArray`.
+  #[inline]
+  pub const fn new(array: [T; N]) -> Self {
+    Self(array)
+  }
+
+  /// Returns the wrapped array.
+  #[inline]
+  pub fn into_inner(self) -> [T; N] {
+    self.0
+  }
+}
+
+impl<T: CheapClone, const N: usize> CheapClone for BigArray<T, N> {
+  const COST: CloneCost = T::COST;
+
+  #[inline]
+  fn cheap_clone(&self) -> Self {
+    Self(crate::cheap_clone_array(&self.0))
+  }
+}
+
+impl<T, const N: usize> From<[T; N]> for BigArray<T, N> {
+  #[inline]
+  fn from(array: [T; N]) -> Self {
+    Self(array)
+  }
+}
+
+impl<T, const N: usize> Deref for BigArray<T, N> {
+  type Target = [T; N];
+
+  #[inline]
+  fn deref(&self) -> &[T; N] {
+    &self.0
+  }
+}
+
+impl<T, const N: usize> DerefMut for BigArray<T, N> {
+  #[inline]
+  fn deref_mut(&mut self) -> &mut [T; N] {
+    &mut self.0
+  }
+}
+
+impl<T, const N: usize> AsRef<[T]> for BigArray<T, N> {
+  #[inline]
+  fn as_ref(&self) -> &[T] {
+    &self.0
+  }
+}
+
+impl<T, const N: usize> AsMut<[T]> for BigArray<T, N> {
+  #[inline]
+  fn as_mut(&mut self) -> &mut [T] {
+    &mut self.0
+  }
+}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for BigArray<T, N> {
+  #[inline]
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    self.0.fmt(f)
+  }
+}
+
+/// Compares and hashes a shared handle by the identity of its pointee instead of its value.
+///
+/// Two `ByAddress` are equal if their handles are [`SharedHandle::ptr_eq`], e.g. clones of the
+/// same [`Arc`](alloc::sync::Arc), which gives identity semantics in `HashSet`s and `BTreeSet`s.
+/// They are hashed and ordered by the address of the pointee, handles at the same address which
+/// are not `ptr_eq` (e.g. [`Bytes`](bytes::Bytes) of different lengths) are ordered by size.
+/// Lookups can use the raw pointer as key, via `Borrow<*const T::Target>`.
+///
+/// ```rust
+/// use cheap_clone::ByAddress;
+/// # #[cfg(feature = "alloc")]
+/// # {
+/// use std::{collections::HashSet, sync::Arc};
+///
+/// let a = Arc::new(1);
+/// let b = Arc::new(1);
+///
+/// let mut visited = HashSet::new();
+/// visited.insert(ByAddress::new(a.clone()));
+/// assert!(visited.contains(&ByAddress::new(a.clone())));
+/// assert!(!visited.contains(&ByAddress::new(b.clone())));
+/// assert!(visited.contains(&Arc::as_ptr(&a)));
+/// # }
+/// ```
+pub struct ByAddress<T: SharedHandle + Deref> {
+  ptr: *const T::Target,
+  value: T,
+}
+
+// SAFETY: the pointer is only used as an address and is never dereferenced.
+unsafe impl<T: SharedHandle + Deref + Send> Send for ByAddress<T> {}
+// SAFETY: the pointer is only used as an address and is never dereferenced.
+unsafe impl<T: SharedHandle + Deref + Sync> Sync for ByAddress<T> {}
+
+impl<T: SharedHandle + Deref> ByAddress<T> {
+  /// Wraps `value` in a `ByAddress`.
+  #[inline]
+  pub fn new(value: T) -> Self {
+    Self {
+      ptr: &*value as *const T::Target,
+      value,
+    }
+  }
+
+  /// Returns the wrapped handle.
+  #[inline]
+  pub fn into_inner(self) -> T {
+    self.value
+  }
+
+  /// Returns the pointer to the pointee, whose address is hashed.
+  #[inline]
+  pub fn as_ptr(&self) -> *const T::Target {
+    self.ptr
+  }
+
+  #[inline]
+  fn addr(&self) -> *const () {
+    self.ptr as *const ()
+  }
+}
+
+impl<T: SharedHandle + Deref> Clone for ByAddress<T> {
+  #[inline]
+  fn clone(&self) -> Self {
+    Self {
+      ptr: self.ptr,
+      value: self.value.clone(),
+    }
+  }
+}
+
+impl<T: SharedHandle + Deref> CheapClone for ByAddress<T> {
+  const COST: CloneCost = T::COST;
+
+  #[inline]
+  fn cheap_clone(&self) -> Self {
+    Self {
+      ptr: self.ptr,
+      value: self.value.cheap_clone(),
+    }
+  }
+}
+
+impl<T: SharedHandle + Deref> From<T> for ByAddress<T> {
+  #[inline]
+  fn from(value: T) -> Self {
+    Self::new(value)
+  }
+}
+
+impl<T: SharedHandle + Deref> Deref for ByAddress<T> {
+  type Target = T;
+
+  #[inline]
+  fn deref(&self) -> &T {
+    &self.value
+  }
+}
+
+impl<T: SharedHandle + Deref> AsRef<T> for ByAddress<T> {
+  #[inline]
+  fn as_ref(&self) -> &T {
+    &self.value
+  }
+}
+
+// Hashing a thin `*const T::Target` only hashes the address, just like `ByAddress` does.
+impl<T: SharedHandle + Deref> Borrow<*const T::Target> for ByAddress<T>
+where
+  T::Target: Sized,
+{
+  #[inline]
+  fn borrow(&self) -> &*const T::Target {
+    &self.ptr
+  }
+}
+
+// Equality is `SharedHandle::ptr_eq`, which can also compare the metadata of the pointer (e.g.
+// the length of a `Bytes`), so two handles at the same address are not necessarily equal.
+impl<T: SharedHandle + Deref> PartialEq for ByAddress<T> {
+  #[inline]
+  fn eq(&self, other: &Self) -> bool {
+    SharedHandle::ptr_eq(&self.value, &other.value)
+  }
+}
+
+impl<T: SharedHandle + Deref> Eq for ByAddress<T> {}
+
+impl<T: SharedHandle + Deref> PartialOrd for ByAddress<T> {
+  #[inline]
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+impl<T: SharedHandle + Deref> Ord for ByAddress<T> {
+  #[inline]
+  fn cmp(&self, other: &Self) -> Ordering {
+    self.addr().cmp(&other.addr()).then_with(|| {
+      if self == other {
+        Ordering::Equal
+      } else {
+        core::mem::size_of_val(&*self.value).cmp(&core::mem::size_of_val(&*other.value))
+      }
+    })
+  }
+}
+
+impl<T: SharedHandle + Deref> Hash for ByAddress<T> {
+  #[inline]
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    self.addr().hash(state)
+  }
+}
+
+impl<T: SharedHandle + Deref + fmt::Debug> fmt::Debug for ByAddress<T> {
+  #[inline]
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    self.value.fmt(f)
+  }
+}
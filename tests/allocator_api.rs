@@ -0,0 +1,75 @@
+#![cfg(feature = "nightly-allocator-api")]
+#![cfg_attr(feature = "nightly-allocator-api", feature(allocator_api))]
+
+use std::{
+  alloc::{AllocError, Allocator, Global, Layout},
+  ptr::NonNull,
+  rc::Rc,
+  sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+  },
+};
+
+use cheap_clone::{impl_cheap_clone, CheapClone, CloneCost};
+
+/// Forwards to the global allocator and counts the allocations.
+#[derive(Clone, Copy)]
+struct Counting<'a>(&'a AtomicUsize);
+
+impl_cheap_clone!(copy: Counting<'_>);
+
+unsafe impl Allocator for Counting<'_> {
+  fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+    self.0.fetch_add(1, Ordering::Relaxed);
+    Global.allocate(layout)
+  }
+
+  unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+    Global.deallocate(ptr, layout)
+  }
+}
+
+#[test]
+fn rc_with_local_allocator() {
+  let allocations = AtomicUsize::new(0);
+  let rc = Rc::new_in(1u32, Counting(&allocations));
+  let weak = Rc::downgrade(&rc);
+
+  let rc2 = rc.cheap_clone();
+  let weak2 = weak.cheap_clone();
+  assert!(Rc::ptr_eq(&rc, &rc2));
+  assert_eq!(Rc::strong_count(&rc), 2);
+  assert_eq!(*weak2.upgrade().unwrap(), 1);
+  assert_eq!(allocations.load(Ordering::Relaxed), 1);
+  assert_eq!(
+    <Rc<u32, Counting<'_>> as CheapClone>::COST,
+    CloneCost::LocalRefCount
+  );
+}
+
+#[test]
+fn arc_with_local_allocator() {
+  let allocations = AtomicUsize::new(0);
+  let arc = Arc::new_in(1u32, Counting(&allocations));
+  let weak = Arc::downgrade(&arc);
+
+  let arc2 = arc.cheap_clone();
+  let weak2 = weak.cheap_clone();
+  assert!(Arc::ptr_eq(&arc, &arc2));
+  assert_eq!(Arc::strong_count(&arc), 2);
+  assert_eq!(*weak2.upgrade().unwrap(), 1);
+  assert_eq!(allocations.load(Ordering::Relaxed), 1);
+  assert_eq!(
+    <Arc<u32, Counting<'_>> as CheapClone>::COST,
+    CloneCost::AtomicRefCount
+  );
+}
+
+#[test]
+fn global_allocator_is_still_supported() {
+  let rc = Rc::new(1u8);
+  assert!(Rc::ptr_eq(&rc, &rc.cheap_clone()));
+  let arc = Arc::new(1u8);
+  assert!(Arc::ptr_eq(&arc, &arc.cheap_clone()));
+}
@@ -0,0 +1,50 @@
+#![cfg(feature = "alloc")]
+
+use std::{collections::HashSet, rc::Rc};
+
+use cheap_clone::{ByAddress, CheapClone};
+
+#[test]
+fn clones_are_equal() {
+  let a = Rc::new(1);
+  let b = Rc::new(1);
+  assert_eq!(
+    ByAddress::new(a.cheap_clone()),
+    ByAddress::new(a.cheap_clone())
+  );
+  assert_ne!(
+    ByAddress::new(a.cheap_clone()),
+    ByAddress::new(b.cheap_clone())
+  );
+
+  let handles: HashSet<_> = [a.cheap_clone(), a.cheap_clone(), b]
+    .into_iter()
+    .map(ByAddress::new)
+    .collect();
+  assert_eq!(handles.len(), 2);
+}
+
+#[cfg(feature = "bytes")]
+#[test]
+fn agrees_with_ptr_eq() {
+  use cheap_clone::SharedHandle;
+  use std::collections::BTreeSet;
+
+  let bytes = bytes::Bytes::from_static(b"abcd");
+  let (short, long) = (bytes.slice(0..1), bytes.slice(0..4));
+  assert!(!SharedHandle::ptr_eq(&short, &long));
+
+  let (short, long) = (ByAddress::new(short), ByAddress::new(long));
+  assert_ne!(short, long);
+  assert_eq!(short, short.cheap_clone());
+  assert!(short < long);
+
+  let hashed: HashSet<_> = [short.cheap_clone(), long.cheap_clone(), short.cheap_clone()]
+    .into_iter()
+    .collect();
+  assert_eq!(hashed.len(), 2);
+  let ordered: BTreeSet<_> = [long.cheap_clone(), short.cheap_clone(), long.cheap_clone()]
+    .into_iter()
+    .collect();
+  assert_eq!(ordered.into_iter().collect::<Vec<_>>(), [short, long]);
+}
@@ -0,0 +1,205 @@
+#![cfg(feature = "proptest")]
+
+use std::{
+  marker::PhantomData,
+  net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6},
+  num::{
+    NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+    NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize,
+  },
+  ptr::NonNull,
+  rc::{self, Rc},
+  sync::{Arc, Weak},
+};
+
+use cheap_clone::{
+  conformance::proptest::{self, prelude::*, sample::select},
+  AssertCheap, BigArray, ByAddress, ByCopy, CheapClone, SharedBox,
+};
+
+fn arc_str() -> impl Strategy<Value = Arc<str>> {
+  any::<String>().prop_map(Arc::from)
+}
+
+/// `Weak`s whose value is still alive, because the strategy owns an `Arc` for every value.
+fn live_weak() -> impl Strategy<Value = Weak<u8>> {
+  let values: Vec<Arc<u8>> = (0..=u8::MAX).map(Arc::new).collect();
+  any::<u8>().prop_map(move |n| Arc::downgrade(&values[usize::from(n)]))
+}
+
+/// `rc::Weak`s whose value is still alive, because the strategy owns an `Rc` for every value.
+fn live_rc_weak() -> impl Strategy<Value = rc::Weak<u8>> {
+  let values: Vec<Rc<u8>> = (0..=u8::MAX).map(Rc::new).collect();
+  any::<u8>().prop_map(move |n| Rc::downgrade(&values[usize::from(n)]))
+}
+
+cheap_clone::conformance! {
+  unit: (), send;
+  boolean: bool, send;
+  character: char, send;
+  float32: f32, send, eq = |a: &f32, b: &f32| a.to_bits() == b.to_bits();
+  float64: f64, send, eq = |a: &f64, b: &f64| a.to_bits() == b.to_bits();
+  str_ref: &'static str = select(vec!["", "a", "cheap"]), send;
+  array: [Arc<u8>; 4], send;
+  big_array: BigArray<u8, 512> = any::<u8>().prop_map(|n| BigArray::new([n; 512])), send;
+  by_copy: ByCopy<u64> = any::<u64>().prop_map(ByCopy::new), send;
+  assert_cheap: AssertCheap<String> = any::<String>().prop_map(AssertCheap::new), send;
+
+  const_ptr: *const u8 = any::<usize>().prop_map(|n| n as *const u8);
+  mut_ptr: *mut u8 = any::<usize>().prop_map(|n| n as *mut u8);
+  phantom: PhantomData<String>, send;
+  non_null: NonNull<u8> = any::<NonZeroUsize>()
+    .prop_map(|n| NonNull::new(n.get() as *mut u8).unwrap());
+
+  ip: IpAddr, send;
+  ipv4: Ipv4Addr, send;
+  ipv6: Ipv6Addr, send;
+  socket: SocketAddr, send;
+  socket_v4: SocketAddrV4, send;
+  socket_v6: SocketAddrV6, send;
+
+  arc: Arc<u8>, shared, send;
+  arc_unsized: Arc<str> = arc_str(), shared, send;
+  rc_value: Rc<Vec<u8>>, shared;
+  weak: Weak<u8> = any::<u8>().prop_map(|n| Arc::downgrade(&Arc::new(n))),
+    shared, send, eq = Weak::ptr_eq;
+  live_weak: Weak<u8> = live_weak(), shared, send, eq = Weak::ptr_eq;
+  rc_weak: rc::Weak<u8> = any::<u8>().prop_map(|n| Rc::downgrade(&Rc::new(n))),
+    shared, eq = rc::Weak::ptr_eq;
+  live_rc_weak: rc::Weak<u8> = live_rc_weak(), shared, eq = rc::Weak::ptr_eq;
+  by_address: ByAddress<Arc<u8>> = any::<Arc<u8>>().prop_map(ByAddress::new), send;
+  shared_box: SharedBox<String> = any::<String>().prop_map(SharedBox::new), shared, send;
+  pinned: std::pin::Pin<Arc<u8>> = any::<u8>().prop_map(Arc::pin), shared, send;
+  pinned_rc: std::pin::Pin<Rc<u8>> = any::<u8>().prop_map(Rc::pin), shared;
+
+  option: Option<Arc<str>> = proptest::option::of(arc_str()), send;
+  result: Result<u8, Arc<str>> = prop_oneof![any::<u8>().prop_map(Ok), arc_str().prop_map(Err)], send;
+  mixed_tuple1: (Arc<u8>,), shared, send;
+  mixed_tuple2: (Arc<u8>, Rc<u8>), shared;
+  mixed_tuple10: (u8, u16, u32, u64, i8, i16, char, bool, (), Arc<u8>), send;
+}
+
+macro_rules! primitives {
+  ($($ty:ident),+ $(,)?) => {
+    paste::paste! {
+      cheap_clone::conformance! {
+        $([< primitive_ $ty:snake >]: $ty, send;)+
+      }
+    }
+  };
+}
+
+primitives! {
+  i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize,
+  NonZeroI8, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI128, NonZeroIsize,
+  NonZeroU8, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU128, NonZeroUsize,
+}
+
+/// The fields of a tuple of `u8`s.
+trait Fields {
+  fn fields(&self) -> Vec<u8>;
+}
+
+/// A tuple of `u8`s compared and printed through its fields, because tuples of more than 12
+/// elements are neither `Debug` nor `PartialEq`.
+#[derive(Clone)]
+struct Tuple<T>(T);
+
+impl<T: CheapClone> CheapClone for Tuple<T> {
+  fn cheap_clone(&self) -> Self {
+    Self(self.0.cheap_clone())
+  }
+}
+
+impl<T: Fields> PartialEq for Tuple<T> {
+  fn eq(&self, other: &Self) -> bool {
+    self.0.fields() == other.0.fields()
+  }
+}
+
+impl<T: Fields> std::fmt::Debug for Tuple<T> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    self.0.fields().fmt(f)
+  }
+}
+
+macro_rules! u8_field {
+  ($idx:tt) => {
+    u8
+  };
+}
+
+macro_rules! tuples {
+  ($($name:ident: $($idx:tt),+;)+) => {
+    $(
+      impl Fields for ($(u8_field!($idx),)+) {
+        fn fields(&self) -> Vec<u8> {
+          vec![$(self.$idx),+]
+        }
+      }
+    )+
+
+    cheap_clone::conformance! {
+      $(
+        $name: Tuple<($(u8_field!($idx),)+)> = any::<u8>()
+          .prop_map(|n| Tuple(($(n.wrapping_add($idx),)+))), send;
+      )+
+    }
+  };
+}
+
+tuples! {
+  tuple1: 0;
+  tuple2: 0, 1;
+  tuple3: 0, 1, 2;
+  tuple4: 0, 1, 2, 3;
+  tuple5: 0, 1, 2, 3, 4;
+  tuple6: 0, 1, 2, 3, 4, 5;
+  tuple7: 0, 1, 2, 3, 4, 5, 6;
+  tuple8: 0, 1, 2, 3, 4, 5, 6, 7;
+  tuple9: 0, 1, 2, 3, 4, 5, 6, 7, 8;
+  tuple10: 0, 1, 2, 3, 4, 5, 6, 7, 8, 9;
+  tuple11: 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10;
+  tuple12: 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11;
+  tuple13: 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12;
+  tuple14: 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13;
+  tuple15: 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14;
+  tuple16: 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15;
+  tuple17: 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16;
+  tuple18: 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17;
+  tuple19: 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18;
+  tuple20: 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19;
+  tuple21: 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20;
+  tuple22: 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21;
+  tuple23: 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22;
+  tuple24: 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23;
+}
+
+#[cfg(feature = "bytes")]
+cheap_clone::conformance! {
+  bytes_buf: bytes::Bytes = any::<Vec<u8>>().prop_map(bytes::Bytes::from), shared, send;
+}
+
+#[cfg(feature = "either")]
+cheap_clone::conformance! {
+  either_value: either::Either<u8, Arc<str>> = prop_oneof![
+    any::<u8>().prop_map(either::Either::Left),
+    arc_str().prop_map(either::Either::Right),
+  ], send;
+}
+
+#[cfg(feature = "smol_str")]
+cheap_clone::conformance! {
+  smol_string: smol_str::SmolStr = any::<String>().prop_map(smol_str::SmolStr::from), send;
+}
+
+#[cfg(feature = "portable-atomic")]
+cheap_clone::conformance! {
+  portable_arc: portable_atomic_util::Arc<u8> =
+    any::<u8>().prop_map(portable_atomic_util::Arc::new), shared, send;
+}
+
+#[cfg(all(feature = "legacy-box", not(feature = "strict")))]
+cheap_clone::conformance! {
+  legacy_box: Box<u8>, send;
+}
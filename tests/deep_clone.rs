@@ -0,0 +1,14 @@
+#![cfg(feature = "bytes")]
+
+use bytes::Bytes;
+use cheap_clone::DeepClone;
+
+#[test]
+fn slice_does_not_keep_parent_alive() {
+  let big = Bytes::from(vec![7u8; 1 << 16]);
+  let copy = big.slice(0..4).deep_clone();
+  assert_ne!(copy.as_ptr(), big.as_ptr());
+
+  drop(big);
+  assert_eq!(copy, [7u8; 4][..]);
+}
@@ -0,0 +1,124 @@
+#![cfg(feature = "derive")]
+
+use core::marker::PhantomData;
+
+use cheap_clone::{CheapClone, CloneCost};
+
+#[derive(Clone, Copy, Debug, PartialEq, CheapClone)]
+struct Named {
+  id: u32,
+  flag: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, CheapClone)]
+struct Tuple(u8, &'static str, Option<char>);
+
+#[derive(Clone, Debug, PartialEq, CheapClone)]
+struct Unit;
+
+#[derive(Clone, Debug, PartialEq, CheapClone)]
+enum Message {
+  Ping,
+  Move(i32, i32),
+  Named { named: Named },
+}
+
+#[derive(Clone, CheapClone)]
+enum Never {}
+
+#[derive(Clone, Debug, PartialEq, CheapClone)]
+struct Generic<T, U> {
+  value: T,
+  values: Option<(U, T)>,
+}
+
+#[derive(Clone, Debug, PartialEq, CheapClone)]
+struct Bounded<T: Copy>
+where
+  T: PartialEq,
+{
+  value: T,
+}
+
+/// A typed handle, whose marker field mentions `T` without storing one.
+#[derive(Clone, Debug, PartialEq, CheapClone)]
+struct Id<T> {
+  raw: u64,
+  marker: PhantomData<T>,
+}
+
+/// Only `Clone`, so it is a `Composite` field.
+#[derive(Clone, Debug, PartialEq)]
+struct Composite;
+
+impl CheapClone for Composite {}
+
+#[derive(Clone, Debug, PartialEq, CheapClone)]
+struct Mixed(u8, Composite);
+
+#[test]
+fn clones_every_field() {
+  let named = Named { id: 1, flag: true };
+  assert_eq!(named.cheap_clone(), named);
+
+  let tuple = Tuple(1, "two", Some('3'));
+  assert_eq!(tuple.cheap_clone(), tuple);
+
+  assert_eq!(Unit.cheap_clone(), Unit);
+}
+
+#[test]
+fn clones_every_variant() {
+  for message in [
+    Message::Ping,
+    Message::Move(-1, 1),
+    Message::Named {
+      named: Named { id: 2, flag: false },
+    },
+  ] {
+    assert_eq!(message.cheap_clone(), message);
+  }
+}
+
+#[test]
+fn empty_enum() {
+  fn clone_never(never: &Never) -> Never {
+    never.cheap_clone()
+  }
+  let _ = clone_never;
+  assert_eq!(<Never as CheapClone>::COST, CloneCost::Copy);
+}
+
+#[test]
+fn infers_bounds_from_fields() {
+  // `Composite` is not `Copy`, and `U = Composite` appears in a field.
+  let generic = Generic {
+    value: 1u8,
+    values: Some((Composite, 2)),
+  };
+  assert_eq!(generic.cheap_clone(), generic);
+
+  // `String` is not `CheapClone`, but `PhantomData<String>` is.
+  let id = Id::<String> {
+    raw: 1,
+    marker: PhantomData,
+  };
+  assert_eq!(id.cheap_clone(), id);
+
+  let bounded = Bounded { value: 'a' };
+  assert_eq!(bounded.cheap_clone(), bounded);
+}
+
+#[test]
+fn folds_field_costs() {
+  assert_eq!(<Named as CheapClone>::COST, CloneCost::Copy);
+  assert_eq!(<Unit as CheapClone>::COST, CloneCost::Copy);
+  assert_eq!(<Message as CheapClone>::COST, CloneCost::Copy);
+  assert_eq!(<Mixed as CheapClone>::COST, CloneCost::Composite);
+  assert_eq!(
+    <Generic<u8, Composite> as CheapClone>::COST,
+    CloneCost::Composite
+  );
+  assert_eq!(<Generic<u8, u16> as CheapClone>::COST, CloneCost::Copy);
+  assert_eq!(<Id<String> as CheapClone>::COST, CloneCost::Copy);
+}
@@ -0,0 +1,94 @@
+#![cfg(feature = "testing")]
+
+use std::{
+  rc::{self, Rc},
+  sync::{self, Arc},
+};
+
+use cheap_clone::{
+  no_alloc_test,
+  testing::{assert_cheap_clone_no_alloc, CountingAllocator},
+  AssertCheap, BigArray, ByCopy, SharedBox,
+};
+
+#[global_allocator]
+static ALLOC: CountingAllocator = CountingAllocator::new();
+
+no_alloc_test!(unit, ());
+no_alloc_test!(bool, true);
+no_alloc_test!(char, 'c');
+no_alloc_test!(f64, 1.5f64);
+no_alloc_test!(u128, u128::MAX);
+no_alloc_test!(isize, -1isize);
+no_alloc_test!(non_zero, core::num::NonZeroU64::new(1).unwrap());
+no_alloc_test!(reference, &String::from("borrowed"));
+no_alloc_test!(array, [1u32; 8]);
+no_alloc_test!(big_array, BigArray::new([1u8; 1024]));
+
+no_alloc_test!(tuple, (1u8, Arc::new(2), Rc::new(3)));
+no_alloc_test!(
+  tuple24,
+  (
+    0u8, 1u8, 2u8, 3u8, 4u8, 5u8, 6u8, 7u8, 8u8, 9u8, 10u8, 11u8, 12u8, 13u8, 14u8, 15u8, 16u8,
+    17u8, 18u8, 19u8, 20u8, 21u8, 22u8, 23u8
+  )
+);
+no_alloc_test!(option, Some(Arc::new(1)));
+no_alloc_test!(result_ok, Ok::<_, Rc<u8>>(Arc::new(1)));
+no_alloc_test!(result_err, Err::<Arc<u8>, _>(Rc::new(1)));
+
+no_alloc_test!(rc, Rc::new(String::from("rc")));
+no_alloc_test!(rc_str, Rc::<str>::from("rc"));
+no_alloc_test!(arc, Arc::new(String::from("arc")));
+no_alloc_test!(arc_slice, Arc::<[u8]>::from(vec![1, 2, 3]));
+no_alloc_test!(pin, Arc::pin(1));
+no_alloc_test!(shared_box, SharedBox::new(String::from("shared")));
+no_alloc_test!(by_copy, ByCopy::new(core::time::Duration::from_secs(1)));
+no_alloc_test!(assert_cheap, AssertCheap::new(Arc::new(1)));
+
+#[test]
+fn weak() {
+  let rc = Rc::new(1);
+  assert_cheap_clone_no_alloc(&Rc::downgrade(&rc));
+  assert_cheap_clone_no_alloc(&rc::Weak::<u8>::new());
+
+  let arc = Arc::new(1);
+  assert_cheap_clone_no_alloc(&Arc::downgrade(&arc));
+  assert_cheap_clone_no_alloc(&sync::Weak::<u8>::new());
+}
+
+#[cfg(feature = "bytes")]
+mod bytes_impls {
+  use super::*;
+
+  no_alloc_test!(from_static, bytes::Bytes::from_static(b"static"));
+
+  // The first clone moves the buffer of a `Vec` into a shared allocation.
+  #[test]
+  #[should_panic(expected = "caused 1 allocation(s)")]
+  fn from_vec_first_clone() {
+    assert_cheap_clone_no_alloc(&bytes::Bytes::from(vec![1u8; 64]));
+  }
+
+  #[test]
+  fn from_vec_after_warmup() {
+    cheap_clone::testing::assert_cheap_clone_no_alloc_after_warmup(&bytes::Bytes::from(vec![
+      1u8;
+      64
+    ]));
+  }
+}
+
+#[cfg(feature = "smol_str")]
+mod smol_str_impls {
+  use super::*;
+
+  no_alloc_test!(inline, smol_str::SmolStr::new("inline"));
+  no_alloc_test!(
+    heap,
+    smol_str::SmolStr::new("a string which is too long to be inlined")
+  );
+}
+
+#[cfg(feature = "either")]
+no_alloc_test!(either, either::Either::<_, u8>::Left(Arc::new(1)));
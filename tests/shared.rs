@@ -0,0 +1,94 @@
+#![cfg(all(feature = "derive", feature = "alloc"))]
+
+use cheap_clone::{shared, CheapClone, CloneCost};
+
+/// A shared configuration.
+#[shared]
+#[derive(Debug, PartialEq)]
+struct Config {
+  name: String,
+  retries: u8,
+}
+
+#[shared(local)]
+struct Session {
+  id: u64,
+}
+
+#[shared]
+struct Pair<K: Clone, V>
+where
+  V: Default,
+{
+  key: K,
+  value: V,
+}
+
+mod visibility {
+  use cheap_clone::shared;
+
+  #[shared]
+  pub struct Handle {
+    pub public: u8,
+    private: u8,
+  }
+
+  impl Handle {
+    pub fn sum(&self) -> u8 {
+      self.public() + self.private()
+    }
+  }
+}
+
+// A `cfg` after `#[shared]` removes every generated item, not only the inner struct.
+#[shared]
+#[cfg(any())]
+struct Removed {
+  missing: Missing,
+}
+
+// An `allow` after `#[shared]` also applies to the handle.
+#[deny(non_camel_case_types)]
+mod lints {
+  use cheap_clone::shared;
+
+  #[shared]
+  #[allow(non_camel_case_types)]
+  pub struct snake_case {
+    pub value: u8,
+  }
+}
+
+#[test]
+fn clones_share_the_data() {
+  let config = Config::new(String::from("worker"), 3);
+  let copy = config.cheap_clone();
+  assert_eq!(copy.name(), "worker");
+  assert_eq!(*copy.retries(), 3);
+  assert!(std::ptr::eq(config.name(), copy.name()));
+  assert_eq!(<Config as CheapClone>::COST, CloneCost::AtomicRefCount);
+
+  let session = Session::new(1);
+  assert!(std::ptr::eq(session.clone().id(), session.id()));
+  assert_eq!(<Session as CheapClone>::COST, CloneCost::LocalRefCount);
+}
+
+#[test]
+fn keeps_generics_and_where_clauses() {
+  let pair = Pair::new("key", vec![1, 2]);
+  let copy = pair.cheap_clone();
+  assert_eq!((*copy.key(), copy.value().as_slice()), ("key", &[1, 2][..]));
+}
+
+#[test]
+fn accessors_keep_field_visibility() {
+  let handle = visibility::Handle::new(1, 2);
+  assert_eq!(*handle.public(), 1);
+  assert_eq!(handle.sum(), 3);
+}
+
+#[test]
+fn cfg_and_allow_apply_to_every_item() {
+  let value = lints::snake_case::new(1);
+  assert_eq!(*value.cheap_clone().value(), 1);
+}